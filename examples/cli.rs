@@ -67,20 +67,18 @@ async fn main() -> Result<()> {
                         println!("🏷️  VERSION: {:?}", version)
                     }
                 },
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    if error_msg.contains("timeout") || error_msg.contains("timed out") {
-                        // Timeout is expected in normal operation - just check connection
-                        if !vmix.is_connected() {
-                            println!("💔 Connection lost");
-                            break;
-                        }
-                        // Connection is still alive, continue listening
-                        continue;
-                    } else {
-                        eprintln!("❌ Error receiving command: {}", e);
+                Err(e) if e.is_timeout() => {
+                    // Timeout is expected in normal operation - just check connection
+                    if !vmix.is_connected() {
+                        println!("💔 Connection lost");
                         break;
                     }
+                    // Connection is still alive, continue listening
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("❌ Error receiving command: {}", e);
+                    break;
                 }
             }
         }
@@ -0,0 +1,94 @@
+//! Compare request latency with and without HTTP keep-alive
+//!
+//! Starts a local HTTP server answering like the vMix API, so no vMix is needed:
+//!
+//! ```sh
+//! cargo run --example http_keepalive --features http
+//! ```
+use anyhow::Result;
+use std::time::{Duration, Instant};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+use vmix_rs::http::HttpVmixClient;
+
+const REQUESTS: usize = 500;
+/// A minimal vMix state, parsed on every request like a real one
+const BODY: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut reader = BufReader::new(reader);
+                let mut line = String::new();
+                let mut close = false;
+                loop {
+                    line.clear();
+                    match reader.read_line(&mut line).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(_) => {}
+                    }
+                    close |= line.trim().eq_ignore_ascii_case("connection: close");
+                    if line.trim().is_empty() {
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+                            BODY.len(),
+                            BODY
+                        );
+                        if writer.write_all(response.as_bytes()).await.is_err() || close {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    println!(
+        "HTTP keep-alive latency ({} requests against {})",
+        REQUESTS, addr
+    );
+    println!("==========================================================");
+
+    let timeout = Duration::from_secs(5);
+    let fresh = measure(&HttpVmixClient::new(addr, timeout).with_keep_alive(false)).await?;
+    let pooled = measure(&HttpVmixClient::new(addr, timeout)).await?;
+
+    print_stats("New connection per request", &fresh);
+    print_stats("Keep-alive connection pool", &pooled);
+    println!(
+        "\nMean speedup: {:.1}x",
+        mean(&fresh).as_secs_f64() / mean(&pooled).as_secs_f64()
+    );
+    Ok(())
+}
+
+async fn measure(client: &HttpVmixClient) -> Result<Vec<Duration>> {
+    let mut latencies = Vec::with_capacity(REQUESTS);
+    for _ in 0..REQUESTS {
+        let start = Instant::now();
+        client.get_xml_state().await?;
+        latencies.push(start.elapsed());
+    }
+    Ok(latencies)
+}
+
+fn mean(latencies: &[Duration]) -> Duration {
+    latencies.iter().sum::<Duration>() / latencies.len() as u32
+}
+
+fn print_stats(label: &str, latencies: &[Duration]) {
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+    println!("\n{}:", label);
+    println!("   mean: {:?}", mean(&sorted));
+    println!("   p50:  {:?}", sorted[sorted.len() / 2]);
+    println!("   p99:  {:?}", sorted[sorted.len() * 99 / 100]);
+    println!("   max:  {:?}", sorted[sorted.len() - 1]);
+}
@@ -105,7 +105,7 @@ async fn main() -> Result<()> {
         }
     }
 
-    println!("   Note: HTTP API does not support real-time event streaming");
+    println!("   Note: HTTP API does not push events; HttpStateWatcher polls for changes instead");
 
     // Test HTTP tally data retrieval for comparison
     println!("\n5. HTTP Tally Data (snapshot):");
@@ -0,0 +1,73 @@
+use vmix_rs::acts::{ActivatorState, ActivatorsData, Bus};
+
+fn acts(lines: &[&str]) -> Vec<ActivatorsData> {
+    lines.iter().map(|line| line.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_state_tracks_activator_deltas() {
+    let mut state = ActivatorState::new();
+    assert_eq!(state.bus_volume(Bus::C), None);
+    assert_eq!(state.recording(), None);
+
+    state.extend(acts(&[
+        "InputMix3 4 1",
+        "InputMix3 5 1",
+        "Input 1 1",
+        "InputPreview 2 1",
+        "BusCVolume 0.5",
+        "Recording 1",
+        "Overlay2 7 1",
+        "InputBusAAudio 1 1",
+    ]));
+    assert_eq!(state.live_inputs(3), vec![4, 5]);
+    assert_eq!(state.live_inputs(1), vec![1]);
+    assert_eq!(state.preview_inputs(1), vec![2]);
+    assert_eq!(state.bus_volume(Bus::C), Some(0.5));
+    assert_eq!(state.recording(), Some(true));
+    assert_eq!(state.overlay_input(2), Some(7));
+    assert_eq!(state.input_bus_audio(1, Bus::A), Some(true));
+
+    state.extend(acts(&[
+        "InputMix3 4 0",
+        "BusCVolume 0.25",
+        "Recording 0",
+        "Overlay2 7 0",
+    ]));
+    assert_eq!(state.live_inputs(3), vec![5]);
+    assert!(!state.is_live(3, 4));
+    assert_eq!(state.bus_volume(Bus::C), Some(0.25));
+    assert_eq!(state.recording(), Some(false));
+    assert_eq!(state.overlay_input(2), None);
+    assert!(state.live_inputs(9).is_empty());
+}
+
+#[test]
+fn test_state_is_seeded_from_snapshot() {
+    let xml = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Video" title="Clip" shortTitle="Clip" state="Running" position="0" duration="0" loop="False" muted="True" volume="50" balance="0" solo="False" soloPFL="False" audiobusses="M,B" meterF1="0" meterF2="0" gainDb="0">Clip</input></inputs><overlays><overlay number="1">2</overlay><overlay number="2" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>True</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><mix number="2"><preview>1</preview><active>2</active></mix><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="80" /><busB volume="25" muted="True" meterF1="0" meterF2="0" solo="True" sendToMaster="False" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+    let vmix = vmix_rs::models::from_str(xml).unwrap();
+
+    let mut state = ActivatorState::from_snapshot(&vmix);
+    assert_eq!(state.live_inputs(1), vec![1]);
+    assert_eq!(state.preview_inputs(1), vec![2]);
+    assert_eq!(state.live_inputs(2), vec![2]);
+    assert!(state.is_playing(2));
+    assert_eq!(state.input_volume(2), Some(0.5));
+    assert_eq!(state.input_audio(2), Some(false));
+    assert_eq!(state.input_master_audio(2), Some(true));
+    assert_eq!(state.input_bus_audio(2, Bus::B), Some(true));
+    assert_eq!(state.input_bus_audio(2, Bus::A), Some(false));
+    assert_eq!(state.overlay_input(1), Some(2));
+    assert_eq!(state.overlay_input(2), None);
+    assert_eq!(state.master_headphones(), Some(0.8));
+    assert_eq!(state.bus_volume(Bus::B), Some(0.25));
+    assert_eq!(state.bus_audio(Bus::B), Some(false));
+    assert_eq!(state.bus_solo(Bus::B), Some(true));
+    assert_eq!(state.bus_volume(Bus::A), None);
+    assert_eq!(state.recording(), Some(true));
+
+    // Deltas apply on top of the snapshot
+    state.apply(&"Input 1 0".parse().unwrap());
+    state.apply(&"Input 2 1".parse().unwrap());
+    assert_eq!(state.live_inputs(1), vec![2]);
+}
@@ -0,0 +1,109 @@
+use vmix_rs::acts::{ActivatorsData, Bus};
+
+#[test]
+fn test_parametric_activators_are_parsed() {
+    let data: ActivatorsData = "InputMix3 12 1".parse().unwrap();
+    assert_eq!(
+        data,
+        ActivatorsData::InputMix {
+            mix: 3,
+            input: 12,
+            active: true
+        }
+    );
+
+    let data: ActivatorsData = "Input 1 0".parse().unwrap();
+    assert_eq!(
+        data,
+        ActivatorsData::InputMix {
+            mix: 1,
+            input: 1,
+            active: false
+        }
+    );
+
+    let data: ActivatorsData = "BusCVolume 0.75".parse().unwrap();
+    assert_eq!(
+        data,
+        ActivatorsData::BusVolume {
+            bus: Bus::C,
+            level: 0.75
+        }
+    );
+
+    let data: ActivatorsData = "InputBusGAudio 4 1".parse().unwrap();
+    assert_eq!(
+        data,
+        ActivatorsData::InputBusAudio {
+            input: 4,
+            bus: Bus::G,
+            active: true
+        }
+    );
+}
+
+#[test]
+fn test_display_round_trips_wire_format() {
+    let lines = [
+        "Input 1 1",
+        "InputMix2 3 0",
+        "InputMix16 1000 1",
+        "InputPreview 2 1",
+        "InputPreviewMix9 5 1",
+        "InputPlaying 7 0",
+        "InputVolume 1 0.5",
+        "InputHeadphones 2 100",
+        "MasterVolume 0.8765432",
+        "MasterHeadphones 1",
+        "BusAVolume 0.25",
+        "InputAudio 3 1",
+        "InputSolo 3 0",
+        "InputBusDAudio 3 1",
+        "InputMasterAudio 3 1",
+        "MasterAudio 1",
+        "BusFAudio 0",
+        "BusBSolo 1",
+        "FadeToBlack 0",
+        "Recording 1",
+        "Streaming 0",
+        "External 1",
+        "Fullscreen 0",
+        "Overlay4 6 1",
+        "ReplayPlaying 1",
+    ];
+    for line in lines {
+        let data: ActivatorsData = line.parse().unwrap();
+        assert!(
+            !matches!(data, ActivatorsData::Unknown(_)),
+            "{} should be recognised",
+            line
+        );
+        assert_eq!(data.to_string(), line);
+    }
+}
+
+#[test]
+fn test_display_normalises_levels() {
+    let data: ActivatorsData = "BusAVolume 0.50".parse().unwrap();
+    assert_eq!(data.to_string(), "BusAVolume 0.5");
+    assert_eq!(data.to_string().parse::<ActivatorsData>().unwrap(), data);
+
+    let data: ActivatorsData = "InputVolume 1 1.0".parse().unwrap();
+    assert_eq!(data.to_string(), "InputVolume 1 1");
+}
+
+#[test]
+fn test_unknown_and_malformed_activators_keep_raw_data() {
+    for line in [
+        "SomethingNew 1 2 3",
+        "InputMix1 1 1",
+        "BusXVolume 0.5",
+        "Input 1",
+        "Input abc 1",
+        "Recording 2",
+    ] {
+        let data: ActivatorsData = line.parse().unwrap();
+        assert!(matches!(data, ActivatorsData::Unknown(_)), "{}", line);
+        assert_eq!(data.to_string(), line);
+    }
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix, spawn_mock_vmix_http};
+use std::time::Duration;
+use vmix_rs::{
+    HttpVmixClient, VmixError,
+    client::{AsyncVmixApi, ClientCapabilities, Transport, VmixClient},
+    commands::{RecvCommand, TallyData},
+    models::functions::VmixFunction,
+    vmix::VmixApi,
+    vmix_tcp::{EventFilter, EventKind},
+};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+fn tcp_client() -> Box<dyn VmixClient> {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "XML" => vec![format!("XML {}\r\n{}\r\n", SNAPSHOT.len() + 2, SNAPSHOT)],
+        "FUNCTION Cut" => vec![
+            "FUNCTION OK Completed\r\n".to_string(),
+            "ACTS OK Input 2 1\r\n".to_string(),
+        ],
+        _ => vec![],
+    });
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    Box::new(AsyncVmixApi::from(api).with_timeout(Duration::from_secs(2)))
+}
+
+#[tokio::test]
+async fn test_tcp_client_through_trait_object() {
+    let client = tcp_client();
+    assert_eq!(
+        client.transport_capabilities(),
+        ClientCapabilities {
+            transport: Transport::Tcp,
+            events: true,
+            function_responses: true,
+        }
+    );
+
+    let events = client
+        .subscribe(EventFilter::only(&[EventKind::Acts]), 16)
+        .await
+        .unwrap();
+    client.execute(&VmixFunction::cut()).await.unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(2), events.recv_async())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(event, RecvCommand::ACTS(_)));
+
+    let state = client.fetch_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+
+    let tally = client.tally().await.unwrap();
+    assert_eq!(tally.get(&1), Some(&TallyData::PROGRAM));
+    assert_eq!(tally.get(&2), Some(&TallyData::PREVIEW));
+    assert!(client.is_connected().await);
+}
+
+#[tokio::test]
+async fn test_http_client_reports_missing_event_support() {
+    let client: Box<dyn VmixClient> = Box::new(HttpVmixClient::new(
+        "127.0.0.1:8088".parse().unwrap(),
+        Duration::from_secs(1),
+    ));
+    let capabilities = client.transport_capabilities();
+    assert_eq!(capabilities.transport, Transport::Http);
+    assert!(!capabilities.events);
+
+    let Err(err) = client.subscribe(EventFilter::all(), 16).await else {
+        panic!("HTTP cannot subscribe to events");
+    };
+    assert!(matches!(err, VmixError::TransportUnsupported { .. }));
+}
+
+#[tokio::test]
+async fn test_tcp_and_http_tally_agree() {
+    // Input 3 is neither live nor in preview
+    let snapshot = SNAPSHOT.replace(
+        "</inputs>",
+        r#"<input key="c" number="3" type="Colour" title="Red" shortTitle="Red" state="Paused" position="0" duration="0" loop="False">Red</input></inputs>"#,
+    );
+
+    let xml = snapshot.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        "XML" => vec![format!("XML {}\r\n{}\r\n", xml.len() + 2, xml)],
+        _ => vec![],
+    });
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let tcp: Box<dyn VmixClient> =
+        Box::new(AsyncVmixApi::from(api).with_timeout(Duration::from_secs(2)));
+
+    let xml = snapshot.clone();
+    let server = spawn_mock_vmix_http(move |_| MockReply::Keep(http_response(&xml)));
+    let http: Box<dyn VmixClient> =
+        Box::new(HttpVmixClient::new(server.addr, Duration::from_secs(2)));
+
+    let tally = tcp.tally().await.unwrap();
+    assert_eq!(tally, http.tally().await.unwrap());
+    assert_eq!(tally.len(), 3);
+    assert_eq!(tally.get(&1), Some(&TallyData::PROGRAM));
+    assert_eq!(tally.get(&2), Some(&TallyData::PREVIEW));
+    assert_eq!(tally.get(&3), Some(&TallyData::OFF));
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use vmix_rs::{
+    commands::{RecvCommand, SendCommand},
+    vmix::VmixApi,
+    vmix_tcp::CommandPriority,
+};
+
+/// Mock vMix answering every function and recording the lines it received
+fn recording_server() -> (std::net::SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = received.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| {
+        log.lock().unwrap().push(line.to_string());
+        match line.split(' ').nth(1) {
+            Some(function) if line.starts_with("FUNCTION") => {
+                vec![format!("FUNCTION OK {}\r\n", function)]
+            }
+            _ => vec![],
+        }
+    });
+    (addr, received)
+}
+
+fn wait_for_lines(received: &Mutex<Vec<String>>, count: usize) -> Vec<String> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while received.lock().unwrap().len() < count && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    received.lock().unwrap().clone()
+}
+
+fn set_volume(input: u16, value: u8) -> SendCommand {
+    SendCommand::FUNCTION(
+        "SetVolume".to_string(),
+        Some(format!("Input={}&Value={}", input, value)),
+    )
+}
+
+#[test]
+fn test_commands_are_sent_in_queue_order_by_default() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr).rate_limit(Some(2)).build().unwrap();
+
+    // The rate limit keeps the text and the overlay waiting in the queue together
+    for input in 1..=2 {
+        api.send_command(set_volume(input, 50)).unwrap();
+    }
+    api.send_command(SendCommand::FUNCTION(
+        "SetText".to_string(),
+        Some("Input=3&SelectedName=Headline.Text&Value=Breaking".to_string()),
+    ))
+    .unwrap();
+    api.send_command(SendCommand::FUNCTION(
+        "OverlayInput1In".to_string(),
+        Some("Input=3".to_string()),
+    ))
+    .unwrap();
+
+    let lines = wait_for_lines(&received, 4);
+    assert_eq!(
+        lines,
+        [
+            "FUNCTION SetVolume Input=1&Value=50",
+            "FUNCTION SetVolume Input=2&Value=50",
+            "FUNCTION SetText Input=3&SelectedName=Headline.Text&Value=Breaking",
+            "FUNCTION OverlayInput1In Input=3",
+        ]
+    );
+}
+
+#[test]
+fn test_switching_commands_jump_the_queue() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr)
+        .rate_limit(Some(10))
+        .prioritize_switching(true)
+        .build()
+        .unwrap();
+
+    for input in 1..=20 {
+        api.send_command(set_volume(input, 50)).unwrap();
+    }
+    api.send_command(SendCommand::FUNCTION("Cut".to_string(), None))
+        .unwrap();
+
+    let lines = wait_for_lines(&received, 21);
+    assert_eq!(lines.len(), 21);
+    let cut = lines.iter().position(|l| l == "FUNCTION Cut").unwrap();
+    // At most the initial burst went out before the Cut was queued
+    assert!(cut <= 11, "Cut sent at position {}", cut);
+}
+
+#[test]
+fn test_fader_updates_are_coalesced_per_input() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr).rate_limit(Some(2)).build().unwrap();
+
+    for value in 1..=50 {
+        api.send_command(set_volume(1, value)).unwrap();
+        api.send_command(set_volume(2, 100 - value)).unwrap();
+    }
+
+    std::thread::sleep(Duration::from_millis(1500));
+    let lines = received.lock().unwrap().clone();
+    let input_1: Vec<&String> = lines.iter().filter(|l| l.contains("Input=1&")).collect();
+    let input_2: Vec<&String> = lines.iter().filter(|l| l.contains("Input=2&")).collect();
+    assert!(input_1.len() <= 3, "{:?}", input_1);
+    assert!(input_2.len() <= 3, "{:?}", input_2);
+    // The latest value always survives
+    assert_eq!(
+        input_1.last().unwrap().as_str(),
+        "FUNCTION SetVolume Input=1&Value=50"
+    );
+    assert_eq!(
+        input_2.last().unwrap().as_str(),
+        "FUNCTION SetVolume Input=2&Value=50"
+    );
+}
+
+#[test]
+fn test_rate_limit_paces_commands() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr)
+        .rate_limit(Some(10))
+        .coalesce("SetVolume", false)
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    for value in 0..15 {
+        api.send_command(set_volume(1, value)).unwrap();
+    }
+    let lines = wait_for_lines(&received, 15);
+    assert_eq!(lines.len(), 15);
+    // A burst of 10, then 5 more at 10 per second
+    assert!(started.elapsed() >= Duration::from_millis(400));
+}
+
+#[test]
+fn test_responses_stay_paired_when_commands_are_reordered() {
+    let (addr, _received) = recording_server();
+    let api = VmixApi::builder(addr)
+        .rate_limit(Some(20))
+        .function_priority("Snapshot", CommandPriority::High)
+        .build()
+        .unwrap();
+
+    for input in 1..=30 {
+        api.send_command(set_volume(input, 10)).unwrap();
+    }
+    let response = api
+        .call_function("Snapshot", None, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(response.body.as_deref(), Some("Snapshot"));
+
+    // Responses to the fire-and-forget commands go to the event stream
+    let event = api.try_receive_command(Duration::from_secs(5)).unwrap();
+    assert!(matches!(
+        event,
+        RecvCommand::FUNCTION(response) if response.body.as_deref() == Some("SetVolume")
+    ));
+}
+
+#[test]
+fn test_coalesced_update_stays_behind_commands_queued_before_it() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr).rate_limit(Some(2)).build().unwrap();
+
+    // Use up the burst so the rest waits in the queue together
+    for input in 2..=3 {
+        api.send_command(set_volume(input, 50)).unwrap();
+    }
+    api.send_command(set_volume(1, 10)).unwrap();
+    api.send_command(SendCommand::FUNCTION("Cut".to_string(), None))
+        .unwrap();
+    api.send_command(set_volume(1, 20)).unwrap();
+
+    let lines = wait_for_lines(&received, 4);
+    assert_eq!(
+        lines,
+        [
+            "FUNCTION SetVolume Input=2&Value=50",
+            "FUNCTION SetVolume Input=3&Value=50",
+            "FUNCTION Cut",
+            "FUNCTION SetVolume Input=1&Value=20",
+        ]
+    );
+}
+
+#[test]
+fn test_queue_is_bounded_by_command_buffer() {
+    let (addr, received) = recording_server();
+    let api = VmixApi::builder(addr)
+        .rate_limit(Some(1))
+        .command_buffer(2)
+        .coalesce("SetVolume", false)
+        .build()
+        .unwrap();
+
+    // The first command goes out at once, the next two fill the queue
+    let started = Instant::now();
+    for value in 0..3 {
+        api.send_command(set_volume(1, value)).unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(started.elapsed() < Duration::from_millis(500));
+
+    // Requests give up once their timeout passes without room in the queue
+    let err = api
+        .call_function("Snapshot", None, Duration::from_millis(200))
+        .unwrap_err();
+    assert!(matches!(err, vmix_rs::VmixError::Timeout(_)));
+
+    // Queueing more waits for the writer to take a command
+    api.send_command(set_volume(1, 3)).unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(800));
+
+    let lines = wait_for_lines(&received, 4);
+    assert!(!lines.iter().any(|l| l.contains("Snapshot")), "{:?}", lines);
+}
@@ -0,0 +1,176 @@
+#![allow(dead_code)]
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+/// Version vMix greets every TCP connection with
+pub const GREETING: &str = "VERSION OK 27.0.0.81\r\n";
+
+/// Spawn a fake vMix TCP server that answers each received line using `respond`
+///
+/// Like vMix, the server sends [`GREETING`] as soon as the connection is accepted.
+pub fn spawn_mock_vmix<F>(respond: F) -> (SocketAddr, JoinHandle<()>)
+where
+    F: Fn(&str) -> Vec<String> + Send + 'static,
+{
+    spawn_mock_vmix_with_greeting(GREETING, Duration::ZERO, respond)
+}
+
+/// Spawn a fake vMix TCP server that sends `greeting` `delay` after accepting the connection
+pub fn spawn_mock_vmix_with_greeting<F>(
+    greeting: &'static str,
+    delay: Duration,
+    respond: F,
+) -> (SocketAddr, JoinHandle<()>)
+where
+    F: Fn(&str) -> Vec<String> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer: TcpStream = stream.try_clone().unwrap();
+        std::thread::sleep(delay);
+        if writer.write_all(greeting.as_bytes()).is_err() {
+            return;
+        }
+        let reader = BufReader::new(stream);
+        for line in reader.lines() {
+            let Ok(line) = line else { break };
+            for reply in respond(line.trim()) {
+                if writer.write_all(reply.as_bytes()).is_err() {
+                    return;
+                }
+            }
+            if line.trim() == "QUIT" {
+                break;
+            }
+        }
+    });
+    (addr, handle)
+}
+
+/// A request received by the fake vMix HTTP server
+pub struct MockRequest {
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// What the fake vMix HTTP server does with a request
+pub enum MockReply {
+    /// Send the raw response and wait for the next request on the connection
+    Keep(String),
+    /// Send the raw response and close the connection
+    Close(String),
+    /// Send raw bytes and wait for the next request on the connection
+    Bytes(Vec<u8>),
+    /// Send the raw response one byte at a time and wait for the next request
+    Trickle(String),
+}
+
+/// A `200 OK` response with `body` and its Content-Length
+pub fn http_response(body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    )
+}
+
+/// Fake vMix HTTP server
+pub struct MockHttpServer {
+    pub addr: SocketAddr,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockHttpServer {
+    /// Number of connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Spawn a fake vMix HTTP server that answers each request using `respond`
+///
+/// Connections stay open between requests unless the request or `respond` asks to close them.
+pub fn spawn_mock_vmix_http<F>(respond: F) -> MockHttpServer
+where
+    F: Fn(&MockRequest) -> MockReply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let respond = Arc::new(respond);
+    let accepted = connections.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { break };
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let respond = respond.clone();
+            std::thread::spawn(move || serve_http(stream, &*respond));
+        }
+    });
+    MockHttpServer { addr, connections }
+}
+
+fn serve_http(stream: TcpStream, respond: &dyn Fn(&MockRequest) -> MockReply) {
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut request = MockRequest {
+            target: request_line.split(' ').nth(1).unwrap_or("").to_string(),
+            headers: Vec::new(),
+        };
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).unwrap_or(0) == 0 {
+                return;
+            }
+            let Some((name, value)) = header.trim().split_once(':') else {
+                break;
+            };
+            request
+                .headers
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+        let mut close = request
+            .header("Connection")
+            .is_some_and(|v| v.eq_ignore_ascii_case("close"));
+        let sent = match respond(&request) {
+            MockReply::Keep(raw) => writer.write_all(raw.as_bytes()),
+            MockReply::Close(raw) => {
+                close = true;
+                writer.write_all(raw.as_bytes())
+            }
+            MockReply::Bytes(raw) => writer.write_all(&raw),
+            MockReply::Trickle(raw) => raw.as_bytes().iter().try_for_each(|byte| {
+                writer.write_all(std::slice::from_ref(byte))?;
+                writer.flush()
+            }),
+        };
+        if sent.is_err() || close {
+            return;
+        }
+    }
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::time::Duration;
+use vmix_rs::{
+    commands::SendCommand,
+    models::{
+        Audiobusses,
+        functions::{InputRef, TitleField, TransitionEffect, VmixFunction},
+    },
+    vmix::VmixApi,
+};
+
+#[test]
+fn test_transition_query_strings() {
+    let function = VmixFunction::Transition {
+        effect: TransitionEffect::Zoom,
+        input: Some(InputRef::from(2)),
+        duration: Some(500),
+        mix: None,
+    };
+    assert_eq!(function.name(), "Zoom");
+    assert_eq!(function.query_string(), "Input=2&Duration=500");
+
+    assert_eq!(VmixFunction::cut().to_string(), "Cut");
+    assert_eq!(
+        VmixFunction::fade(Some(1000)).to_string(),
+        "Fade Duration=1000"
+    );
+}
+
+#[test]
+fn test_values_are_percent_encoded() {
+    let function = VmixFunction::SetText {
+        input: InputRef::from("Lower Third"),
+        field: TitleField::Name("Name.Text".to_string()),
+        value: "Café & Co".to_string(),
+    };
+    assert_eq!(
+        function.query_string(),
+        "Input=Lower%20Third&SelectedName=Name.Text&Value=Caf%C3%A9%20%26%20Co"
+    );
+}
+
+#[test]
+fn test_master_bus_uses_master_functions() {
+    let master = VmixFunction::BusAudioOn {
+        bus: Audiobusses::M,
+    };
+    assert_eq!(master.name(), "MasterAudioON");
+    assert!(master.params().is_empty());
+
+    let bus = VmixFunction::SetBusVolume {
+        bus: Audiobusses::B,
+        volume: 80,
+    };
+    assert_eq!(bus.name(), "SetBusBVolume");
+    assert_eq!(bus.query_string(), "Value=80");
+}
+
+#[test]
+fn test_custom_function_keeps_parameter_order() {
+    let function = VmixFunction::Custom {
+        name: "SetDynamicValue1".to_string(),
+        params: vec![("Value".to_string(), "a/b".to_string())],
+    };
+    assert_eq!(function.to_string(), "SetDynamicValue1 Value=a%2Fb");
+
+    let command = SendCommand::from(&function);
+    assert!(matches!(
+        command,
+        SendCommand::FUNCTION(name, Some(query))
+            if name == "SetDynamicValue1" && query == "Value=a%2Fb"
+    ));
+}
+
+#[test]
+fn test_execute_sends_function_over_tcp() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "FUNCTION SetText Input=1&SelectedName=Title.Text&Value=%E3%81%82" => {
+            vec!["FUNCTION OK Completed\r\n".to_string()]
+        }
+        _ => vec!["FUNCTION ER Unexpected\r\n".to_string()],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let function = VmixFunction::SetText {
+        input: InputRef::Number(1),
+        field: TitleField::Name("Title.Text".to_string()),
+        value: "あ".to_string(),
+    };
+    let response = api.execute(&function, Duration::from_secs(2)).unwrap();
+    assert_eq!(response.body.as_deref(), Some("Completed"));
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::time::Duration;
+use vmix_rs::{
+    commands::SendCommand,
+    models::query::{FunctionParams, decode, encode},
+    vmix::VmixApi,
+};
+
+const SAMPLES: &[&str] = &[
+    "",
+    "Hello world",
+    "Q&A = 50% + more",
+    "テロップ 本番",
+    "a/b?c#d",
+    "line\r\nbreak",
+    "emoji 🎬",
+    "-._~",
+];
+
+#[test]
+fn test_encode_matches_urlencoding() {
+    for sample in SAMPLES {
+        assert_eq!(encode(sample), urlencoding::encode(sample), "{:?}", sample);
+    }
+}
+
+#[test]
+fn test_decode_round_trip() {
+    for sample in SAMPLES {
+        assert_eq!(decode(&encode(sample)).as_deref(), Some(*sample));
+        assert_eq!(
+            decode(&urlencoding::encode(sample)).as_deref(),
+            Some(*sample)
+        );
+    }
+    // Lowercase escapes are accepted as well
+    assert_eq!(decode("%e3%81%82").as_deref(), Some("あ"));
+    assert_eq!(decode("a+b").as_deref(), Some("a+b"));
+}
+
+#[test]
+fn test_decode_rejects_invalid_input() {
+    assert_eq!(decode("%"), None);
+    assert_eq!(decode("%4"), None);
+    assert_eq!(decode("%zz"), None);
+    // Not UTF-8
+    assert_eq!(decode("%FF"), None);
+}
+
+#[test]
+fn test_params_round_trip_through_query_string() {
+    let params = FunctionParams::new()
+        .with("Input", 2)
+        .with("SelectedName", "Name.Text")
+        .with("Value", "Q&A = テスト")
+        .with_optional("Duration", None::<u32>)
+        .with_optional("Mix", Some(3));
+    let query = params.to_query_string();
+    assert_eq!(
+        query,
+        "Input=2&SelectedName=Name.Text&Value=Q%26A%20%3D%20%E3%83%86%E3%82%B9%E3%83%88&Mix=3"
+    );
+
+    let parsed = FunctionParams::parse(&query).unwrap();
+    assert_eq!(parsed, params);
+    assert_eq!(parsed.get("Value"), Some("Q&A = テスト"));
+    assert_eq!(
+        parsed.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+        ["Input", "SelectedName", "Value", "Mix"]
+    );
+}
+
+#[test]
+fn test_function_command_encodes_params() {
+    let params = FunctionParams::new()
+        .with("Input", 1)
+        .with("Value", "a b&c");
+    let bytes: Vec<u8> = SendCommand::function("SetText", &params).into();
+    assert_eq!(bytes, b"FUNCTION SetText Input=1&Value=a%20b%26c\r\n");
+
+    let bytes: Vec<u8> = SendCommand::function("Cut", &FunctionParams::new()).into();
+    assert_eq!(bytes, b"FUNCTION Cut\r\n");
+}
+
+#[test]
+fn test_non_ascii_values_survive_the_wire() {
+    let (addr, _server) = spawn_mock_vmix(|line| {
+        let Some(query) = line.strip_prefix("FUNCTION SetText ") else {
+            return vec!["FUNCTION ER Unexpected\r\n".to_string()];
+        };
+        // Echo the decoded value back, as a UTF-8 response line
+        let params = FunctionParams::parse(query).unwrap();
+        vec![format!("FUNCTION OK {}\r\n", params.get("Value").unwrap())]
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let params = FunctionParams::new()
+        .with("Input", 1)
+        .with("SelectedName", "Title.Text")
+        .with("Value", "速報 & ニュース");
+    let response = api
+        .call_function_with_params("SetText", &params, Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(response.body.as_deref(), Some("速報 & ニュース"));
+}
+
+#[test]
+fn test_typed_builders() {
+    let params = FunctionParams::new()
+        .input("Camera 1")
+        .selected_name("Title.Text")
+        .value("Live")
+        .duration(500)
+        .mix(2)
+        .selected_index(3);
+    assert_eq!(
+        params.to_query_string(),
+        "Input=Camera%201&SelectedName=Title.Text&Value=Live&Duration=500&Mix=2&SelectedIndex=3"
+    );
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_validate_rejects_unknown_names() {
+    let params = FunctionParams::new().with("input", 1).with("VALUE", 2);
+    assert!(params.validate().is_ok());
+
+    let err = FunctionParams::new()
+        .input(1)
+        .with("Volume", 50)
+        .validate()
+        .unwrap_err();
+    assert_eq!(err.name, "Volume");
+    assert!(err.to_string().contains("Volume"));
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use vmix_rs::{
+    health::{HealthMonitor, HeartbeatConfig},
+    vmix::VmixApi,
+};
+
+/// Poll `condition` until it holds or `timeout` passes
+fn wait_for(timeout: Duration, condition: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    condition()
+}
+
+#[test]
+fn test_heartbeat_measures_latency() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "VERSION" => vec!["VERSION OK 27.0.0.81\r\n".to_string()],
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let monitor = HealthMonitor::start(
+        api,
+        HeartbeatConfig::new(Duration::from_millis(20)).samples(4),
+    );
+
+    assert!(wait_for(Duration::from_secs(2), || {
+        monitor
+            .latency()
+            .is_some_and(|latency| latency.samples == 4)
+    }));
+    let health = monitor.health();
+    assert!(health.alive);
+    assert_eq!(health.missed, 0);
+    assert!(health.last_seen.is_some());
+    assert!(health.last_heartbeat.is_some());
+
+    let latency = health.latency.unwrap();
+    assert!(latency.min <= latency.mean && latency.mean <= latency.max);
+    assert!(latency.max < Duration::from_secs(1));
+}
+
+#[test]
+fn test_unanswered_heartbeats_mark_connection_dead() {
+    // Answers the first two heartbeats, then goes silent with the socket still open
+    let answered = Arc::new(AtomicUsize::new(0));
+    let server_answered = answered.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        "VERSION" if server_answered.fetch_add(1, Ordering::SeqCst) < 2 => {
+            vec!["VERSION OK 27.0.0.81\r\n".to_string()]
+        }
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let monitor = HealthMonitor::start(
+        api.clone(),
+        HeartbeatConfig::new(Duration::from_millis(20))
+            .timeout(Duration::from_millis(50))
+            .max_missed(2),
+    );
+
+    assert!(wait_for(Duration::from_secs(2), || monitor
+        .latency()
+        .is_some_and(|latency| latency.samples == 2)));
+    assert!(wait_for(Duration::from_secs(2), || !monitor.is_alive()));
+
+    // The socket itself still looks fine
+    assert!(api.is_connected());
+    let health = monitor.health();
+    assert!(health.missed >= 2);
+    assert_eq!(health.latency.unwrap().samples, 2);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{MockReply, MockRequest, http_response, spawn_mock_vmix_http};
+use std::time::Duration;
+use vmix_rs::{VmixError, http::HttpVmixClient, vmix_http::Credentials};
+
+/// Server protected with `expected` as the Authorization header
+fn protected(expected: &'static str) -> impl Fn(&MockRequest) -> MockReply {
+    move |request| {
+        match request.header("Authorization") {
+        Some(value) if value == expected => MockReply::Keep(http_response("OK")),
+        _ => MockReply::Keep(
+            "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"vMix\"\r\nContent-Length: 0\r\n\r\n"
+                .to_string(),
+        ),
+    }
+    }
+}
+
+#[tokio::test]
+async fn test_basic_auth_header_is_sent() {
+    // "admin:s3cret:pa ss"
+    let server = spawn_mock_vmix_http(protected("Basic YWRtaW46czNjcmV0OnBhIHNz"));
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2))
+        .with_basic_auth("admin", "s3cret:pa ss");
+
+    client.cut().await.unwrap();
+    assert!(client.is_connected().await);
+}
+
+#[tokio::test]
+async fn test_basic_auth_encoding_is_padded() {
+    // "a:"
+    let server = spawn_mock_vmix_http(protected("Basic YTo="));
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2))
+        .with_credentials(Some(Credentials::new("a", "")));
+
+    client.cut().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_wrong_password_is_unauthorized() {
+    let server = spawn_mock_vmix_http(protected("Basic YWRtaW46czNjcmV0OnBhIHNz"));
+    let client =
+        HttpVmixClient::new(server.addr, Duration::from_secs(2)).with_basic_auth("admin", "wrong");
+
+    let err = client.cut().await.unwrap_err();
+    assert!(matches!(err, VmixError::Unauthorized), "{:?}", err);
+    assert!(!client.is_connected().await);
+}
+
+#[tokio::test]
+async fn test_missing_credentials_are_unauthorized() {
+    let server = spawn_mock_vmix_http(protected("Basic YWRtaW46czNjcmV0OnBhIHNz"));
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Unauthorized), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_no_authorization_header_without_credentials() {
+    let server = spawn_mock_vmix_http(|request| {
+        assert_eq!(request.header("Authorization"), None);
+        MockReply::Keep(http_response("OK"))
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    client.cut().await.unwrap();
+}
+
+#[test]
+fn test_credentials_are_not_leaked() {
+    let client = HttpVmixClient::new_with_host_port("127.0.0.1", 8088, Duration::from_secs(2))
+        .with_basic_auth("admin", "s3cret");
+
+    assert_eq!(client.get_base_url(), "http://127.0.0.1:8088/api");
+    let debug = format!("{:?}", client);
+    assert!(debug.contains("admin"), "{}", debug);
+    assert!(!debug.contains("s3cret"), "{}", debug);
+
+    let credentials = Credentials::new("admin", "s3cret");
+    assert_eq!(credentials.username(), "admin");
+    assert!(!format!("{:?}", credentials).contains("s3cret"));
+}
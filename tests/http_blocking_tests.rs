@@ -0,0 +1,193 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use vmix_rs::{
+    BlockingHttpVmixClient, VmixError,
+    models::VmixVersion,
+    vmix_http::{FunctionParams, RetryPolicy, TallyData},
+};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+fn client(server: &common::MockHttpServer) -> BlockingHttpVmixClient {
+    BlockingHttpVmixClient::new(server.addr, Duration::from_secs(2))
+}
+
+#[test]
+fn test_functions_and_state() {
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let recorded = targets.clone();
+    let server = spawn_mock_vmix_http(move |request| {
+        recorded.lock().unwrap().push(request.target.clone());
+        if request.target == "/api" {
+            MockReply::Keep(http_response(SNAPSHOT))
+        } else {
+            MockReply::Keep(http_response(""))
+        }
+    });
+    let client = client(&server);
+
+    client.cut().unwrap();
+    client
+        .execute_function_with_params("SetText", &FunctionParams::new().input(1).value("a b"))
+        .unwrap();
+    assert_eq!(client.get_xml_state().unwrap().version, "27.0.0.81");
+    assert_eq!(client.get_active_input().unwrap(), 1);
+    assert_eq!(client.get_preview_input().unwrap(), 2);
+    let tally = client.get_tally_data().unwrap();
+    assert_eq!(tally.get(&1), Some(&TallyData::PROGRAM));
+    assert_eq!(tally.get(&2), Some(&TallyData::PREVIEW));
+
+    assert_eq!(
+        targets.lock().unwrap()[..2],
+        [
+            "/api?Function=Cut",
+            "/api?Function=SetText&Input=1&Value=a%20b"
+        ]
+    );
+    // Every request used the same connection
+    assert_eq!(server.connections(), 1);
+    assert_eq!(client.idle_connections(), 1);
+}
+
+#[test]
+fn test_clones_are_shared_across_threads() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("")));
+    let client = client(&server);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            std::thread::spawn(move || {
+                for _ in 0..5 {
+                    client.cut().unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(server.connections() <= 4);
+    assert!(client.idle_connections() >= 1);
+}
+
+#[test]
+fn test_closed_idle_connection_is_replaced() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Close(http_response("")));
+    let client = client(&server);
+
+    // A cut on a connection closed after it was sent is not sent again, so
+    // let the close arrive first
+    for _ in 0..3 {
+        client.cut().unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(server.connections(), 3);
+}
+
+#[test]
+fn test_chunked_response() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Trickle(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nHello\r\n6\r\n world\r\n0\r\n\r\n"
+                .to_string(),
+        )
+    });
+    let client = client(&server);
+
+    assert_eq!(
+        client.xml_text("vmix/inputs/input/@title").unwrap(),
+        "Hello world"
+    );
+    assert_eq!(client.idle_connections(), 1);
+}
+
+#[test]
+fn test_error_statuses() {
+    let server = spawn_mock_vmix_http(|request| {
+        let status = match request.header("Authorization") {
+            None => "401 Unauthorized",
+            Some(_) => "500 Internal Server Error",
+        };
+        MockReply::Keep(format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status))
+    });
+
+    let err = client(&server).cut().unwrap_err();
+    assert!(matches!(err, VmixError::Unauthorized), "{:?}", err);
+
+    let err = client(&server)
+        .with_basic_auth("admin", "secret")
+        .cut()
+        .unwrap_err();
+    assert!(
+        matches!(err, VmixError::HttpStatus { status: 500, .. }),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_timeout() {
+    let server = spawn_mock_vmix_http(|_| {
+        std::thread::sleep(Duration::from_secs(1));
+        MockReply::Keep(http_response(""))
+    });
+    let client = BlockingHttpVmixClient::new(server.addr, Duration::from_millis(100));
+
+    let start = Instant::now();
+    let err = client.cut().unwrap_err();
+    assert!(matches!(err, VmixError::Timeout(_)), "{:?}", err);
+    assert!(start.elapsed() < Duration::from_millis(900));
+}
+
+#[test]
+fn test_reads_are_retried() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counted = requests.clone();
+    let server = spawn_mock_vmix_http(move |_| {
+        if counted.fetch_add(1, Ordering::SeqCst) < 2 {
+            MockReply::Keep(
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n".to_string(),
+            )
+        } else {
+            MockReply::Keep(http_response(SNAPSHOT))
+        }
+    });
+    let client = client(&server).with_retry(
+        RetryPolicy::new(3).backoff(Duration::from_millis(10), Duration::from_millis(20)),
+    );
+
+    assert!(client.get_xml_state().is_ok());
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_detect_capabilities() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response(SNAPSHOT)));
+    let mut client = client(&server);
+
+    let capabilities = client.detect_capabilities().unwrap();
+    assert_eq!(capabilities.version(), VmixVersion::new(27, 0, 0, 81));
+    assert_eq!(client.capabilities(), Some(capabilities));
+}
+
+#[test]
+fn test_connection_refused() {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = BlockingHttpVmixClient::new(addr, Duration::from_secs(1));
+
+    assert!(!client.is_connected());
+    assert!(matches!(client.cut(), Err(VmixError::Connect { .. })));
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::time::Duration;
+use vmix_rs::{VmixError, http::HttpVmixClient};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+fn client(server: &common::MockHttpServer) -> HttpVmixClient {
+    HttpVmixClient::new(server.addr, Duration::from_secs(2))
+}
+
+/// `body` in chunks of `size` bytes, with an extension on the first chunk and a trailer
+fn chunked(body: &str, size: usize) -> String {
+    let mut raw = "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nTransfer-Encoding: chunked\r\n\r\n"
+        .to_string();
+    for (i, chunk) in body.as_bytes().chunks(size).enumerate() {
+        let extension = if i == 0 { ";name=value" } else { "" };
+        raw.push_str(&format!("{:x}{}\r\n", chunk.len(), extension));
+        raw.push_str(std::str::from_utf8(chunk).unwrap());
+        raw.push_str("\r\n");
+    }
+    raw.push_str("0\r\nX-Trailer: done\r\n\r\n");
+    raw
+}
+
+#[tokio::test]
+async fn test_chunked_body() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(chunked(SNAPSHOT, 100)));
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+    assert_eq!(state.inputs.input.len(), 2);
+
+    // The connection is reused after the last chunk
+    client.get_xml_state().await.unwrap();
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn test_chunked_body_split_at_every_byte() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Trickle(chunked(SNAPSHOT, 7)));
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.active, "1");
+}
+
+#[tokio::test]
+async fn test_content_length_body_split_at_every_byte() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Trickle(http_response(SNAPSHOT)));
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.preview, "2");
+}
+
+#[tokio::test]
+async fn test_body_read_with_headers_is_kept() {
+    // Headers and body arrive in a single write
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response(SNAPSHOT)));
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+}
+
+#[tokio::test]
+async fn test_body_until_close() {
+    let server =
+        spawn_mock_vmix_http(|_| MockReply::Close(format!("HTTP/1.1 200 OK\r\n\r\n{}", SNAPSHOT)));
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+    assert_eq!(client.idle_connections(), 0);
+}
+
+#[tokio::test]
+async fn test_truncated_body_is_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Close(format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+            SNAPSHOT.len(),
+            &SNAPSHOT[..100]
+        ))
+    });
+    let client = client(&server);
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_truncated_chunked_body_is_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        let raw = chunked(SNAPSHOT, 100);
+        MockReply::Close(raw[..raw.len() - 20].to_string())
+    });
+    let client = client(&server);
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_invalid_chunk_size_is_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Keep("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n".to_string())
+    });
+    let client = client(&server);
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_conflicting_content_lengths_are_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Keep(
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nContent-Length: 3\r\n\r\nOK".to_string(),
+        )
+    });
+    let client = client(&server);
+
+    let err = client.cut().await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_max_body_size() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(chunked(SNAPSHOT, 100)));
+    let client = client(&server).with_max_body_size(256);
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(
+        matches!(err, VmixError::BodyTooLarge { limit: 256 }),
+        "{:?}",
+        err
+    );
+    assert_eq!(client.idle_connections(), 0);
+}
+
+#[tokio::test]
+async fn test_unsupported_content_encoding_is_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Keep(
+            "HTTP/1.1 200 OK\r\nContent-Encoding: br\r\nContent-Length: 2\r\n\r\nOK".to_string(),
+        )
+    });
+    let client = client(&server);
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn test_gzip_body() {
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(SNAPSHOT.as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+    let server = spawn_mock_vmix_http(move |_| {
+        let mut raw = format!(
+            "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n",
+            compressed.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(&compressed);
+        MockReply::Bytes(raw)
+    });
+    let client = client(&server);
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::{MockReply, MockRequest, http_response, spawn_mock_vmix_http};
+use std::{collections::HashMap, time::Duration};
+use vmix_rs::{
+    HttpVmixClient, VmixApiClient, VmixError,
+    models::functions::{InputRef, VmixFunction},
+    vmix_http::{FunctionParams, HttpResponse},
+};
+
+/// Query parameters of a request, decoded
+fn params(request: &MockRequest) -> FunctionParams {
+    let (path, query) = request
+        .target
+        .split_once('?')
+        .unwrap_or((&request.target, ""));
+    assert_eq!(path, "/api");
+    FunctionParams::parse(query).unwrap()
+}
+
+fn server() -> common::MockHttpServer {
+    spawn_mock_vmix_http(|request| {
+        let params = params(request);
+        let body = match (params.get("Function"), params.get("XPath")) {
+            (Some("Cut"), _) => String::new(),
+            (Some(function), _) => format!("{} {}", function, params.get("Input").unwrap_or("")),
+            (None, Some("vmix/inputs/input[@number='1']/@title")) => {
+                "Camera 1 & Co\r\n".to_string()
+            }
+            (None, Some(_)) => String::new(),
+            (None, None) => return MockReply::Keep(http_response("<vmix></vmix>")),
+        };
+        MockReply::Keep(http_response(&body))
+    })
+}
+
+#[tokio::test]
+async fn test_execute_function_with_response() {
+    let server = server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let mut params = HashMap::new();
+    params.insert("Input".to_string(), "2".to_string());
+    let response = client
+        .execute_function_with_response("PreviewInput", &params)
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        HttpResponse {
+            status: 200,
+            body: "PreviewInput 2".to_string(),
+        }
+    );
+
+    let response = client
+        .execute_function_with_response("Cut", &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert!(response.body.is_empty());
+}
+
+#[tokio::test]
+async fn test_execute_with_response() {
+    let server = server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let response = client
+        .execute_with_response(&VmixFunction::ActiveInput {
+            input: InputRef::Number(3),
+            mix: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.body, "ActiveInput 3");
+}
+
+#[tokio::test]
+async fn test_xml_text() {
+    let server = server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let title = client
+        .xml_text("vmix/inputs/input[@number='1']/@title")
+        .await
+        .unwrap();
+    assert_eq!(title, "Camera 1 & Co");
+
+    let missing = client.xml_text("vmix/nothing").await.unwrap();
+    assert_eq!(missing, "");
+}
+
+#[tokio::test]
+async fn test_through_trait() {
+    let server = server();
+    let client: Box<dyn VmixApiClient + Send + Sync> =
+        Box::new(HttpVmixClient::new(server.addr, Duration::from_secs(2)));
+
+    let title = client
+        .xml_text("vmix/inputs/input[@number='1']/@title")
+        .await
+        .unwrap();
+    assert_eq!(title, "Camera 1 & Co");
+    let response = client
+        .execute_function_with_response("Fade", &HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(response.body, "Fade ");
+}
+
+#[tokio::test]
+async fn test_invalid_utf8_body_is_an_error() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Bytes(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n\xff\xfe".to_vec())
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let err = client
+        .execute_function_with_response("Cut", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, VmixError::Utf8(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_error_status_keeps_body() {
+    let server = spawn_mock_vmix_http(|_| {
+        let body = "Input not found\r\n";
+        MockReply::Keep(format!(
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ))
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let err = client
+        .execute_function("PreviewInput", &HashMap::new())
+        .await
+        .unwrap_err();
+    match &err {
+        VmixError::HttpStatus { status, body } => {
+            assert_eq!(*status, 500);
+            assert_eq!(body, "Input not found");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(
+        err.to_string(),
+        "HTTP request failed with status: 500: Input not found"
+    );
+}
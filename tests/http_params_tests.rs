@@ -0,0 +1,145 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use vmix_rs::{
+    HttpVmixClient, VmixError,
+    models::functions::{InputRef, TitleField, VmixFunction},
+    vmix_http::FunctionParams,
+};
+
+/// A client and the targets of the requests it sent
+fn recorded() -> (HttpVmixClient, Arc<Mutex<Vec<String>>>) {
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let recorded = targets.clone();
+    let server = spawn_mock_vmix_http(move |request| {
+        recorded.lock().unwrap().push(request.target.clone());
+        MockReply::Keep(http_response(""))
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+    (client, targets)
+}
+
+#[tokio::test]
+async fn test_params_keep_their_order() {
+    let (client, targets) = recorded();
+
+    let params = FunctionParams::new()
+        .value("Q&A 1/2")
+        .selected_name("Title.Text")
+        .input("Camera 1");
+    client
+        .execute_function_with_params("SetText", &params)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        targets.lock().unwrap().as_slice(),
+        ["/api?Function=SetText&Value=Q%26A%201%2F2&SelectedName=Title.Text&Input=Camera%201"]
+    );
+}
+
+#[tokio::test]
+async fn test_repeated_params() {
+    let (client, targets) = recorded();
+
+    let params = FunctionParams::new().input(1).input(2).duration(500);
+    client
+        .execute_function_with_params("Fade", &params)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        targets.lock().unwrap().as_slice(),
+        ["/api?Function=Fade&Input=1&Input=2&Duration=500"]
+    );
+}
+
+#[tokio::test]
+async fn test_map_params_are_sorted() {
+    let (client, targets) = recorded();
+
+    let mut params = HashMap::new();
+    params.insert("Value".to_string(), "10".to_string());
+    params.insert("Input".to_string(), "3".to_string());
+    params.insert("Mix".to_string(), "1".to_string());
+    for _ in 0..3 {
+        client.execute_function("SetVolume", &params).await.unwrap();
+    }
+
+    let targets = targets.lock().unwrap();
+    assert_eq!(targets.len(), 3);
+    assert!(
+        targets
+            .iter()
+            .all(|t| t == "/api?Function=SetVolume&Input=3&Mix=1&Value=10")
+    );
+}
+
+#[tokio::test]
+async fn test_catalog_functions_use_the_same_query_as_tcp() {
+    let (client, targets) = recorded();
+
+    let function = VmixFunction::SetText {
+        input: InputRef::Number(1),
+        field: TitleField::Name("Title.Text".to_string()),
+        value: "Hello world".to_string(),
+    };
+    client.execute(&function).await.unwrap();
+
+    assert_eq!(
+        targets.lock().unwrap().as_slice(),
+        [format!("/api?Function=SetText&{}", function.query_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_unknown_param_is_not_sent() {
+    let (client, targets) = recorded();
+
+    let params = FunctionParams::new().input(1).with("Duraton", 500);
+    let err = client
+        .execute_function_with_params("Fade", &params)
+        .await
+        .unwrap_err();
+
+    match err {
+        VmixError::UnknownParam(e) => assert_eq!(e.name, "Duraton"),
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(targets.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_function_url_can_be_replayed() {
+    let (client, targets) = recorded();
+
+    let params = FunctionParams::new().input(2).value("50%");
+    let url = client.function_url("SetVolume", &params).unwrap();
+    assert_eq!(
+        url,
+        format!(
+            "{}?Function=SetVolume&Input=2&Value=50%25",
+            client.get_base_url()
+        )
+    );
+
+    // Replay the logged URL
+    let query = url.split_once('?').unwrap().1;
+    let mut logged = FunctionParams::parse(query).unwrap().into_iter();
+    let (_, function) = logged.next().unwrap();
+    let replayed: FunctionParams = logged.collect();
+    client
+        .execute_function_with_params(&function, &replayed)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        targets.lock().unwrap().as_slice(),
+        ["/api?Function=SetVolume&Input=2&Value=50%25"]
+    );
+}
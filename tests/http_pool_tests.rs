@@ -0,0 +1,162 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use vmix_rs::{http::HttpVmixClient, models::functions::VmixFunction};
+
+fn client(server: &common::MockHttpServer) -> HttpVmixClient {
+    HttpVmixClient::new(server.addr, Duration::from_secs(2))
+}
+
+#[tokio::test]
+async fn test_requests_reuse_one_connection() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("<vmix></vmix>")));
+    let client = client(&server);
+
+    for _ in 0..5 {
+        client.execute(&VmixFunction::cut()).await.unwrap();
+    }
+    assert!(client.is_connected().await);
+
+    assert_eq!(server.connections(), 1);
+    assert_eq!(client.idle_connections(), 1);
+}
+
+#[tokio::test]
+async fn test_clones_share_connections() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("<vmix></vmix>")));
+    let client = client(&server);
+    let clone = client.clone();
+
+    client.cut().await.unwrap();
+    clone.cut().await.unwrap();
+
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn test_without_keep_alive_every_request_connects() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("<vmix></vmix>")));
+    let client = client(&server).with_keep_alive(false);
+
+    for _ in 0..3 {
+        client.cut().await.unwrap();
+    }
+
+    assert_eq!(server.connections(), 3);
+    assert_eq!(client.idle_connections(), 0);
+}
+
+#[tokio::test]
+async fn test_reconnects_when_server_closed_idle_connection() {
+    // The server closes every connection after one response, without saying so
+    let server = spawn_mock_vmix_http(|_| MockReply::Close(http_response("<vmix></vmix>")));
+    let client = client(&server);
+
+    client.cut().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    client.cut().await.unwrap();
+
+    assert_eq!(server.connections(), 2);
+}
+
+#[tokio::test]
+async fn test_function_is_not_resent_after_connection_closed_without_response() {
+    // The server reads the second request and closes without replying
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let server = spawn_mock_vmix_http(move |_| {
+        if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockReply::Keep(http_response("<vmix></vmix>"))
+        } else {
+            MockReply::Close(String::new())
+        }
+    });
+    let client = client(&server);
+
+    assert!(client.is_connected().await);
+    assert!(client.cut().await.is_err());
+
+    // vMix may have executed the cut, so it must not be sent again
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_read_is_resent_after_connection_closed_without_response() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let server = spawn_mock_vmix_http(move |_| {
+        if counter.fetch_add(1, Ordering::SeqCst) == 1 {
+            MockReply::Close(String::new())
+        } else {
+            MockReply::Keep(http_response("<vmix></vmix>"))
+        }
+    });
+    let client = client(&server);
+
+    assert!(client.is_connected().await);
+    assert!(client.is_connected().await);
+
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    assert_eq!(server.connections(), 2);
+}
+
+#[tokio::test]
+async fn test_response_closing_connection_is_not_reused() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Close(
+            "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\nOK".to_string(),
+        )
+    });
+    let client = client(&server);
+
+    client.cut().await.unwrap();
+    assert_eq!(client.idle_connections(), 0);
+    client.cut().await.unwrap();
+
+    assert_eq!(server.connections(), 2);
+}
+
+#[tokio::test]
+async fn test_idle_connections_expire() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("<vmix></vmix>")));
+    let client = client(&server).with_idle_timeout(Duration::from_millis(50));
+
+    client.cut().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.cut().await.unwrap();
+
+    assert_eq!(server.connections(), 2);
+}
+
+#[tokio::test]
+async fn test_max_idle_connections_limits_pool() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response("<vmix></vmix>")));
+    let client = client(&server).with_max_idle_connections(1);
+
+    // Concurrent requests need a connection each
+    let (a, b, c) = tokio::join!(client.cut(), client.cut(), client.cut());
+    a.unwrap();
+    b.unwrap();
+    c.unwrap();
+
+    assert_eq!(server.connections(), 3);
+    assert_eq!(client.idle_connections(), 1);
+}
+
+#[tokio::test]
+async fn test_error_status_closes_connection() {
+    let server = spawn_mock_vmix_http(|_| {
+        MockReply::Keep("HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n".into())
+    });
+    let client = client(&server);
+
+    assert!(client.cut().await.is_err());
+    assert_eq!(client.idle_connections(), 0);
+}
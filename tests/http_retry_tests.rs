@@ -0,0 +1,193 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use vmix_rs::{
+    VmixError,
+    http::HttpVmixClient,
+    vmix_http::{CircuitBreakerConfig, CircuitState, RetryPolicy},
+};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n";
+
+/// A server failing with `failure` for the first `failures` requests, with the request count
+fn flaky(failures: usize, failure: &'static str) -> (HttpVmixClient, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counted = requests.clone();
+    let server = spawn_mock_vmix_http(move |_| {
+        if counted.fetch_add(1, Ordering::SeqCst) < failures {
+            MockReply::Keep(failure.to_string())
+        } else {
+            MockReply::Keep(http_response(SNAPSHOT))
+        }
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+    (client, requests)
+}
+
+fn fast_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(max_attempts).backoff(Duration::from_millis(5), Duration::from_millis(20))
+}
+
+#[tokio::test]
+async fn test_reads_are_retried() {
+    let (client, requests) = flaky(2, UNAVAILABLE);
+    let client = client.with_retry(fast_retries(3));
+
+    let state = client.get_xml_state().await.unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retries_give_up_after_max_attempts() {
+    let (client, requests) = flaky(usize::MAX, UNAVAILABLE);
+    let client = client.with_retry(fast_retries(3));
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(
+        matches!(err, VmixError::HttpStatus { status: 503, .. }),
+        "{:?}",
+        err
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_functions_are_not_retried_by_default() {
+    let (client, requests) = flaky(1, UNAVAILABLE);
+    let client = client.with_retry(fast_retries(3));
+
+    assert!(client.cut().await.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_functions_are_retried_when_opted_in() {
+    let (client, requests) = flaky(1, UNAVAILABLE);
+    let client = client.with_retry(fast_retries(3).retry_functions(true));
+
+    client.cut().await.unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let (client, requests) = flaky(
+        usize::MAX,
+        "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n",
+    );
+    let client = client.with_retry(fast_retries(3));
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Unauthorized), "{:?}", err);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_refused_connections_are_retried() {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = HttpVmixClient::new(addr, Duration::from_secs(2)).with_retry(fast_retries(3));
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::Connect { .. }), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_fails_fast_while_open() {
+    let (client, requests) = flaky(usize::MAX, UNAVAILABLE);
+    let client = client.with_circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+
+    assert!(client.get_xml_state().await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert!(client.get_xml_state().await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    let err = client.clone().get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::CircuitOpen { .. }), "{:?}", err);
+    assert!(!client.is_connected().await);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_closes_after_successful_trial() {
+    let (client, requests) = flaky(2, UNAVAILABLE);
+    let client =
+        client.with_circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_millis(50)));
+
+    assert!(client.get_xml_state().await.is_err());
+    assert!(client.get_xml_state().await.is_err());
+    assert!(matches!(
+        client.get_xml_state().await,
+        Err(VmixError::CircuitOpen { .. })
+    ));
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+    client.get_xml_state().await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_reopens_after_failed_trial() {
+    let (client, requests) = flaky(usize::MAX, UNAVAILABLE);
+    let client =
+        client.with_circuit_breaker(CircuitBreakerConfig::new(1, Duration::from_millis(50)));
+
+    assert!(client.get_xml_state().await.is_err());
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert!(client.get_xml_state().await.is_err());
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert!(matches!(
+        client.get_xml_state().await,
+        Err(VmixError::CircuitOpen { .. })
+    ));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_retries_stop_when_circuit_opens() {
+    let (client, requests) = flaky(usize::MAX, UNAVAILABLE);
+    let client = client
+        .with_retry(fast_retries(5))
+        .with_circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+    let err = client.get_xml_state().await.unwrap_err();
+    assert!(matches!(err, VmixError::CircuitOpen { .. }), "{:?}", err);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_failing_functions_do_not_open_the_circuit() {
+    // vMix answers a function it cannot execute with 500
+    let (client, requests) = flaky(
+        usize::MAX,
+        "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n",
+    );
+    let client = client
+        .with_retry(fast_retries(3))
+        .with_circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+    for _ in 0..5 {
+        let err = client.cut().await.unwrap_err();
+        assert!(
+            matches!(err, VmixError::HttpStatus { status: 500, .. }),
+            "{:?}",
+            err
+        );
+    }
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(requests.load(Ordering::SeqCst), 5);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use vmix_rs::{BlockingHttpVmixClient, HttpVmixClient, VmixError, vmix_http::TallyData};
+
+/// Answer `/tallyupdate/` like vMix's web tally page expects
+fn tally_server() -> (common::MockHttpServer, Arc<Mutex<Vec<String>>>) {
+    let targets = Arc::new(Mutex::new(Vec::new()));
+    let recorded = targets.clone();
+    let server = spawn_mock_vmix_http(move |request| {
+        recorded.lock().unwrap().push(request.target.clone());
+        let colour = match request.target.strip_prefix("/tallyupdate/?key=") {
+            Some("program") => "#ff0000",
+            Some("preview") => "#00ff00",
+            Some("off") => "#000000",
+            _ => return MockReply::Keep(http_response("tallyChange(\"unknown\");")),
+        };
+        MockReply::Keep(http_response(&format!("tallyChange(\"{}\");", colour)))
+    });
+    (server, targets)
+}
+
+#[tokio::test]
+async fn test_tally_for_key() {
+    let (server, targets) = tally_server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    assert_eq!(
+        client.tally_for_key("program").await.unwrap(),
+        TallyData::PROGRAM
+    );
+    assert_eq!(
+        client.tally_for_key("preview").await.unwrap(),
+        TallyData::PREVIEW
+    );
+    assert_eq!(client.tally_for_key("off").await.unwrap(), TallyData::OFF);
+    assert_eq!(targets.lock().unwrap()[0], "/tallyupdate/?key=program");
+}
+
+#[tokio::test]
+async fn test_tally_key_is_encoded() {
+    let (server, targets) = tally_server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let _ = client.tally_for_key("a b&c").await;
+    assert_eq!(targets.lock().unwrap()[0], "/tallyupdate/?key=a%20b%26c");
+}
+
+#[tokio::test]
+async fn test_unexpected_colour_is_an_error() {
+    let (server, _) = tally_server();
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    let err = client.tally_for_key("missing").await.unwrap_err();
+    assert!(matches!(err, VmixError::Protocol(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_colour_formats() {
+    let cases = [
+        ("tallyChange(\"#F44336\");", TallyData::PROGRAM),
+        ("tallyChange(\"#4caf50\");", TallyData::PREVIEW),
+        ("tallyChange(\"#f00\");", TallyData::PROGRAM),
+        ("tallyChange(\"#333333\");", TallyData::OFF),
+        ("Red", TallyData::PROGRAM),
+        ("green\r\n", TallyData::PREVIEW),
+        ("", TallyData::OFF),
+    ];
+    for (body, expected) in cases {
+        let server = spawn_mock_vmix_http(move |_| MockReply::Keep(http_response(body)));
+        let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+        assert_eq!(
+            client.tally_for_key("k").await.unwrap(),
+            expected,
+            "{}",
+            body
+        );
+    }
+}
+
+#[test]
+fn test_blocking_tally_for_key() {
+    let (server, _) = tally_server();
+    let client = BlockingHttpVmixClient::new(server.addr, Duration::from_secs(2));
+
+    assert_eq!(client.tally_for_key("program").unwrap(), TallyData::PROGRAM);
+    assert_eq!(client.tally_for_key("preview").unwrap(), TallyData::PREVIEW);
+}
@@ -0,0 +1,275 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    future::Future,
+    io::{self, Read, Write},
+    pin::{Pin, pin},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+use tokio::io::{AsyncRead as _, AsyncWrite as _, ReadBuf};
+use vmix_rs::{
+    HttpVmixClient, VmixApiClient, VmixError,
+    http::{FuturesIoTransport, HttpTransport, TokioTransport},
+    vmix_http::{
+        RetryPolicy,
+        futures_io::{AsyncRead, AsyncWrite},
+    },
+};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+/// A tokio stream seen through the `futures-io` traits, as async-std and smol streams are
+struct FuturesStream(tokio::net::TcpStream);
+
+impl AsyncRead for FuturesStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        Pin::new(&mut self.0)
+            .poll_read(cx, &mut buf)
+            .map_ok(|()| buf.filled().len())
+    }
+}
+
+impl AsyncWrite for FuturesStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+fn futures_client(
+    server: &common::MockHttpServer,
+    request_timeout: Duration,
+) -> HttpVmixClient<FuturesIoTransport<FuturesStream>> {
+    let transport = FuturesIoTransport::new(
+        |addr| async move {
+            tokio::net::TcpStream::connect(addr)
+                .await
+                .map(FuturesStream)
+        },
+        tokio::time::sleep,
+    );
+    HttpVmixClient::new_with_transport(
+        &server.addr.ip().to_string(),
+        server.addr.port(),
+        request_timeout,
+        transport,
+    )
+}
+
+#[tokio::test]
+async fn test_futures_io_transport() {
+    let server = spawn_mock_vmix_http(|request| {
+        if request.target == "/api" {
+            MockReply::Keep(http_response(SNAPSHOT))
+        } else {
+            MockReply::Keep(http_response(""))
+        }
+    });
+    let client = futures_client(&server, Duration::from_secs(2));
+
+    client.cut().await.unwrap();
+    assert_eq!(client.get_active_input().await.unwrap(), 1);
+    assert!(client.is_connected().await);
+
+    assert_eq!(server.connections(), 1);
+    assert_eq!(client.idle_connections(), 1);
+}
+
+#[tokio::test]
+async fn test_futures_io_transport_replaces_closed_connections() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Close(http_response("")));
+    let client = futures_client(&server, Duration::from_secs(2));
+
+    // A cut on a connection closed after it was sent is not sent again, so
+    // let the close arrive first
+    for _ in 0..3 {
+        client.cut().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(server.connections(), 3);
+}
+
+#[tokio::test]
+async fn test_futures_io_transport_times_out() {
+    let server = spawn_mock_vmix_http(|_| {
+        std::thread::sleep(Duration::from_secs(1));
+        MockReply::Keep(http_response(""))
+    });
+    let client = futures_client(&server, Duration::from_millis(100));
+
+    let err = client.cut().await.unwrap_err();
+    assert!(matches!(err, VmixError::Timeout(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_clients_share_the_api_trait() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response(SNAPSHOT)));
+    let clients: Vec<Box<dyn VmixApiClient + Send + Sync>> = vec![
+        Box::new(HttpVmixClient::new(server.addr, Duration::from_secs(2))),
+        Box::new(futures_client(&server, Duration::from_secs(2))),
+    ];
+
+    for client in &clients {
+        assert_eq!(client.get_preview_input().await.unwrap(), 2);
+    }
+}
+
+/// The tokio transport, counting what the client asks of it
+#[derive(Debug, Clone, Default)]
+struct CountingTransport {
+    connects: Arc<AtomicUsize>,
+    sleeps: Arc<AtomicUsize>,
+}
+
+impl HttpTransport for CountingTransport {
+    type Connection = tokio::net::TcpStream;
+
+    async fn connect(&self, addr: &str) -> io::Result<Self::Connection> {
+        self.connects.fetch_add(1, Ordering::SeqCst);
+        TokioTransport.connect(addr).await
+    }
+
+    async fn read(&self, connection: &mut Self::Connection, buf: &mut [u8]) -> io::Result<usize> {
+        TokioTransport.read(connection, buf).await
+    }
+
+    async fn write_all(&self, connection: &mut Self::Connection, buf: &[u8]) -> io::Result<()> {
+        TokioTransport.write_all(connection, buf).await
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.sleeps.fetch_add(1, Ordering::SeqCst);
+        TokioTransport.sleep(duration).await
+    }
+
+    fn is_open(&self, connection: &mut Self::Connection) -> bool {
+        TokioTransport.is_open(connection)
+    }
+}
+
+#[tokio::test]
+async fn test_custom_transport_is_used_for_retries() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counted = requests.clone();
+    let server = spawn_mock_vmix_http(move |_| {
+        if counted.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockReply::Close(
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n".to_string(),
+            )
+        } else {
+            MockReply::Keep(http_response(SNAPSHOT))
+        }
+    });
+    let transport = CountingTransport::default();
+    let client = HttpVmixClient::new_with_transport(
+        &server.addr.ip().to_string(),
+        server.addr.port(),
+        Duration::from_secs(2),
+        transport.clone(),
+    )
+    .with_retry(RetryPolicy::new(2).backoff(Duration::from_millis(10), Duration::from_millis(10)));
+
+    client.get_xml_state().await.unwrap();
+
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    assert_eq!(transport.connects.load(Ordering::SeqCst), 2);
+    // One backoff, plus one timeout timer for each connect and read
+    assert!(transport.sleeps.load(Ordering::SeqCst) >= 1);
+}
+
+/// A std stream that blocks in `poll_read`, so no reactor is needed
+struct BlockingStream(std::net::TcpStream);
+
+impl AsyncRead for BlockingStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.0.read(buf))
+    }
+}
+
+impl AsyncWrite for BlockingStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.0.write(buf))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.0.flush())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.0.shutdown(std::net::Shutdown::Both))
+    }
+}
+
+struct ThreadWaker(std::thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future on the current thread, without any async runtime
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+#[test]
+fn test_runs_without_tokio() {
+    let server = spawn_mock_vmix_http(|_| MockReply::Keep(http_response(SNAPSHOT)));
+    let transport = FuturesIoTransport::new(
+        |addr| async move { std::net::TcpStream::connect(addr).map(BlockingStream) },
+        // Timeouts never fire; reads block instead
+        |_| std::future::pending(),
+    );
+    let client = HttpVmixClient::new_with_transport(
+        &server.addr.ip().to_string(),
+        server.addr.port(),
+        Duration::from_secs(2),
+        transport,
+    )
+    // Checking whether an idle connection is still open would block
+    .with_keep_alive(false);
+
+    let state = block_on(client.get_xml_state()).unwrap();
+    assert_eq!(state.version, "27.0.0.81");
+    block_on(client.cut()).unwrap();
+    assert_eq!(server.connections(), 2);
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use common::{MockReply, http_response, spawn_mock_vmix_http};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::broadcast::Receiver;
+use vmix_rs::{
+    http::{HttpStateWatcher, HttpVmixClient, WatchConfig, WatchEvent},
+    models::StateChange,
+};
+
+const SNAPSHOT: &str = r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Colour" title="White" shortTitle="White" state="Paused" position="0" duration="0" loop="False">White</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>1</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>False</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#;
+
+/// A server answering with the current `state`, or 500 while it is `None`
+fn watched(state: &Arc<Mutex<Option<String>>>) -> HttpStateWatcher {
+    let served = state.clone();
+    let server = spawn_mock_vmix_http(move |_| match served.lock().unwrap().as_deref() {
+        Some(xml) => MockReply::Keep(http_response(xml)),
+        None => MockReply::Keep(
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n".to_string(),
+        ),
+    });
+    let client = HttpVmixClient::new(server.addr, Duration::from_secs(2));
+    HttpStateWatcher::start(
+        client,
+        WatchConfig::new(Duration::from_millis(20)).jitter(Duration::from_millis(10)),
+    )
+}
+
+async fn wait_for_snapshot(watcher: &HttpStateWatcher) {
+    tokio::time::timeout(Duration::from_secs(2), async {
+        while watcher.snapshot().is_none() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+async fn next(events: &mut Receiver<WatchEvent>) -> WatchEvent {
+    tokio::time::timeout(Duration::from_secs(2), events.recv())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_emits_changes_between_polls() {
+    let state = Arc::new(Mutex::new(Some(SNAPSHOT.to_string())));
+    let watcher = watched(&state);
+    let mut events = watcher.subscribe();
+    wait_for_snapshot(&watcher).await;
+    assert!(watcher.is_reachable());
+    assert_eq!(
+        watcher.read(|vmix| vmix.active.clone()),
+        Some("1".to_string())
+    );
+
+    *state.lock().unwrap() = Some(
+        SNAPSHOT
+            .replace(
+                "<preview>2</preview><active>1</active>",
+                "<preview>1</preview><active>2</active>",
+            )
+            .replace(
+                "<recording>False</recording>",
+                "<recording>True</recording>",
+            ),
+    );
+
+    let mut changes = Vec::new();
+    while changes.len() < 3 {
+        match next(&mut events).await {
+            WatchEvent::Change(change) => changes.push(change),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+    assert!(changes.contains(&StateChange::Active {
+        mix: 1,
+        input: "2".to_string(),
+    }));
+    assert!(changes.contains(&StateChange::Preview {
+        mix: 1,
+        input: "1".to_string(),
+    }));
+    assert!(changes.contains(&StateChange::Recording(true)));
+    assert_eq!(
+        watcher.read(|vmix| vmix.active.clone()),
+        Some("2".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_emits_input_added_and_removed() {
+    let state = Arc::new(Mutex::new(Some(SNAPSHOT.to_string())));
+    let watcher = watched(&state);
+    let mut events = watcher.subscribe();
+    wait_for_snapshot(&watcher).await;
+
+    let added = SNAPSHOT.replace(
+        "</inputs>",
+        r#"<input key="c" number="3" type="Colour" title="Red" shortTitle="Red" state="Paused" position="0" duration="0" loop="False">Red</input></inputs>"#,
+    );
+    *state.lock().unwrap() = Some(added);
+    assert_eq!(
+        next(&mut events).await,
+        WatchEvent::Change(StateChange::InputAdded {
+            key: "c".to_string(),
+            number: "3".to_string(),
+        })
+    );
+
+    *state.lock().unwrap() = Some(SNAPSHOT.to_string());
+    assert_eq!(
+        next(&mut events).await,
+        WatchEvent::Change(StateChange::InputRemoved {
+            key: "c".to_string(),
+            number: "3".to_string(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_reports_unreachable_and_recovery() {
+    let state = Arc::new(Mutex::new(Some(SNAPSHOT.to_string())));
+    let watcher = watched(&state);
+    let mut events = watcher.subscribe();
+    wait_for_snapshot(&watcher).await;
+
+    *state.lock().unwrap() = None;
+    assert!(matches!(
+        next(&mut events).await,
+        WatchEvent::Unreachable(_)
+    ));
+    assert!(!watcher.is_reachable());
+    // The last good state is kept
+    assert!(watcher.snapshot().is_some());
+
+    // Sent once, not on every failed poll
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    *state.lock().unwrap() = Some(SNAPSHOT.replace("<streaming>False", "<streaming>True"));
+    assert_eq!(next(&mut events).await, WatchEvent::Reachable);
+    assert_eq!(
+        next(&mut events).await,
+        WatchEvent::Change(StateChange::Streaming(true))
+    );
+    assert!(watcher.is_reachable());
+}
+
+#[tokio::test]
+async fn test_no_events_while_nothing_changes() {
+    let state = Arc::new(Mutex::new(Some(SNAPSHOT.to_string())));
+    let watcher = watched(&state);
+    let mut events = watcher.subscribe();
+    wait_for_snapshot(&watcher).await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(events.try_recv().is_err());
+    assert!(watcher.is_running());
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use vmix_rs::{live_state::LiveState, models::StateChange, vmix::VmixApi};
+
+fn snapshot_xml(active: u16, streaming: bool) -> String {
+    let streaming = if streaming { "True" } else { "False" };
+    let body = format!(
+        r#"<vmix><version>27.0.0.81</version><edition>4K</edition><inputs><input key="a" number="1" type="Colour" title="Black" shortTitle="Black" state="Paused" position="0" duration="0" loop="False">Black</input><input key="b" number="2" type="Video" title="Clip" shortTitle="Clip" state="Paused" position="0" duration="0" loop="False" muted="False" volume="100" balance="0" solo="False" soloPFL="False" audiobusses="M" meterF1="0" meterF2="0" gainDb="0">Clip</input></inputs><overlays><overlay number="1" /></overlays><preview>2</preview><active>{}</active><fadeToBlack>False</fadeToBlack><transitions><transition number="1" effect="Fade" duration="500" /></transitions><recording>False</recording><external>False</external><streaming>{}</streaming><playList>False</playList><multiCorder>False</multiCorder><fullscreen>False</fullscreen><audio><master volume="100" muted="False" meterF1="0" meterF2="0" headphonesVolume="100" /><busA volume="100" muted="False" meterF1="0" meterF2="0" solo="False" sendToMaster="False" /></audio><dynamic><input1></input1><input2></input2><input3></input3><input4></input4><value1></value1><value2></value2><value3></value3><value4></value4></dynamic></vmix>"#,
+        active, streaming
+    );
+    format!("XML {}\r\n{}\r\n", body.len() + 2, body)
+}
+
+fn wait_for(
+    changes: &vmix_rs::vmix_tcp::Subscription<StateChange>,
+    expected: &StateChange,
+) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while Instant::now() < deadline {
+        if let Ok(change) = changes.recv_timeout(Duration::from_millis(100))
+            && &change == expected
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn test_live_state_applies_deltas() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "XML" => vec![snapshot_xml(1, false)],
+        "FUNCTION Cut" => vec![
+            "ACTS OK Input 1 0\r\n".to_string(),
+            "ACTS OK Input 2 1\r\n".to_string(),
+            "ACTS OK InputPlaying 2 1\r\n".to_string(),
+            "ACTS OK BusAVolume 0.5\r\n".to_string(),
+            "ACTS OK Recording 1\r\n".to_string(),
+            "FUNCTION OK Completed\r\n".to_string(),
+        ],
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let live = LiveState::start(api.clone(), Duration::from_secs(60)).unwrap();
+    assert_eq!(live.read(|vmix| vmix.active.clone()), "1");
+
+    let changes = live.subscribe(64);
+    api.call_function("Cut", None, Duration::from_secs(2))
+        .unwrap();
+
+    assert!(wait_for(&changes, &StateChange::Recording(true)));
+    let vmix = live.snapshot();
+    assert_eq!(vmix.active, "2");
+    assert!(vmix.recording);
+    assert!(matches!(
+        vmix.inputs.input[1].state,
+        vmix_rs::models::State::Running
+    ));
+    assert_eq!(vmix.audio.bus_a.unwrap().volume, 50.0);
+}
+
+#[test]
+fn test_live_state_resyncs_periodically() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let server_fetches = fetches.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        // Streaming starts without an activator, only the next snapshot shows it
+        "XML" => {
+            let first = server_fetches.fetch_add(1, Ordering::SeqCst) == 0;
+            vec![snapshot_xml(1, !first)]
+        }
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let live = LiveState::start(api, Duration::from_millis(200)).unwrap();
+    let changes = live.subscribe(64);
+    assert!(!live.read(|vmix| vmix.streaming));
+
+    assert!(wait_for(&changes, &StateChange::Streaming(true)));
+    assert!(live.read(|vmix| vmix.streaming));
+    assert!(fetches.load(Ordering::SeqCst) >= 2);
+}
+
+#[test]
+fn test_live_state_resyncs_after_unreadable_snapshot() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let server_fetches = fetches.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        "XML" => {
+            let first = server_fetches.fetch_add(1, Ordering::SeqCst) == 0;
+            vec![snapshot_xml(1, !first)]
+        }
+        "FUNCTION Cut" => vec![
+            "XML 9\r\n<vmix><\r\n".to_string(),
+            "FUNCTION OK Completed\r\n".to_string(),
+        ],
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let live = LiveState::start(api.clone(), Duration::from_secs(60)).unwrap();
+    let changes = live.subscribe(64);
+    api.call_function("Cut", None, Duration::from_secs(2))
+        .unwrap();
+
+    // Only a new snapshot, well before the resync interval, shows streaming
+    assert!(wait_for(&changes, &StateChange::Streaming(true)));
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_live_state_backs_off_from_unreadable_snapshots() {
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let server_fetches = fetches.clone();
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        "XML" => {
+            if server_fetches.fetch_add(1, Ordering::SeqCst) == 0 {
+                vec![snapshot_xml(1, false)]
+            } else {
+                vec!["XML 9\r\n<vmix><\r\n".to_string()]
+            }
+        }
+        // Every tally misses an input of the snapshot and asks for a new one
+        "FUNCTION Cut" => {
+            let mut replies = vec!["TALLY OK 1\r\n".to_string(); 50];
+            replies.push("FUNCTION OK Completed\r\n".to_string());
+            replies
+        }
+        _ => vec![],
+    });
+
+    let api = Arc::new(VmixApi::new(addr, Duration::from_secs(2)).unwrap());
+    let live = LiveState::start(api.clone(), Duration::from_millis(50)).unwrap();
+    api.call_function("Cut", None, Duration::from_secs(2))
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_millis(1500);
+    while Instant::now() < deadline {
+        live.resync();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // The first snapshot, then retries 250ms, 500ms and 1s apart at most
+    let fetches = fetches.load(Ordering::SeqCst);
+    assert!((2..=5).contains(&fetches), "{fetches} snapshots requested");
+    assert!(live.is_running());
+}
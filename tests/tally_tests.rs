@@ -0,0 +1,70 @@
+use vmix_rs::{
+    commands::{ActivatorsResponse, RecvCommand, Status, TallyData, TallyResponse},
+    tally::TallyTracker,
+};
+
+fn tally_event(body: &str) -> RecvCommand {
+    RecvCommand::TALLY(TallyResponse {
+        status: Status::OK,
+        body: body
+            .chars()
+            .enumerate()
+            .map(|(i, c)| (i as u16 + 1, TallyData::from(c)))
+            .collect(),
+    })
+}
+
+fn acts_event(line: &str) -> RecvCommand {
+    RecvCommand::ACTS(ActivatorsResponse {
+        status: Status::OK,
+        body: line.parse().unwrap(),
+    })
+}
+
+#[test]
+fn test_mix_tally_is_derived_from_activators() {
+    let mut tracker = TallyTracker::new();
+    assert_eq!(tracker.apply_event(&tally_event("1200")), Some(1));
+    assert_eq!(tracker.apply_event(&acts_event("InputMix2 3 1")), Some(2));
+    assert_eq!(
+        tracker.apply_event(&acts_event("InputPreviewMix2 4 1")),
+        Some(2)
+    );
+    assert_eq!(tracker.apply_event(&acts_event("Recording 1")), None);
+
+    let mix2 = tracker.tally(2);
+    assert_eq!(mix2.len(), 4);
+    assert_eq!(mix2[&1], TallyData::OFF);
+    assert_eq!(mix2[&3], TallyData::PROGRAM);
+    assert_eq!(mix2[&4], TallyData::PREVIEW);
+
+    // The main mix still comes from TALLY
+    let main = tracker.tally(1);
+    assert_eq!(main[&1], TallyData::PROGRAM);
+    assert_eq!(main[&2], TallyData::PREVIEW);
+
+    // Cutting on mix 2 moves the lights
+    tracker.apply_event(&acts_event("InputMix2 3 0"));
+    tracker.apply_event(&acts_event("InputMix2 4 1"));
+    tracker.apply_event(&acts_event("InputPreviewMix2 4 0"));
+    tracker.apply_event(&acts_event("InputPreviewMix2 3 1"));
+    assert_eq!(tracker.input_tally(2, 4), TallyData::PROGRAM);
+    assert_eq!(tracker.input_tally(2, 3), TallyData::PREVIEW);
+    assert_eq!(tracker.input_tally(2, 1), TallyData::OFF);
+    assert_eq!(tracker.input_tally(1, 2), TallyData::PREVIEW);
+    assert_eq!(tracker.mixes(), vec![1, 2]);
+}
+
+#[test]
+fn test_main_mix_falls_back_to_activators() {
+    let mut tracker = TallyTracker::new();
+    tracker.apply_event(&acts_event("Input 5 1"));
+    tracker.apply_event(&acts_event("InputPreview 5 1"));
+    tracker.apply_event(&acts_event("InputPreview 6 1"));
+
+    // Live wins over preview
+    assert_eq!(tracker.input_tally(1, 5), TallyData::PROGRAM);
+    assert_eq!(tracker.input_tally(1, 6), TallyData::PREVIEW);
+    assert_eq!(tracker.tally(1).len(), 2);
+    assert!(tracker.tally(3).is_empty());
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+    time::Duration,
+};
+use vmix_rs::{
+    commands::{RecvCommand, SUBSCRIBECommand},
+    vmix::VmixApi,
+    vmix_tcp::{EventFilter, EventKind, RecvError},
+};
+
+/// Mock that emits a burst of `count` TALLY events and one ACTS event before
+/// acknowledging `FUNCTION Sync`, so all events are published once the call returns
+fn burst_server(count: usize) -> std::net::SocketAddr {
+    let (addr, _server) = spawn_mock_vmix(move |line| match line {
+        "FUNCTION Sync" => {
+            let mut replies: Vec<String> = (0..count)
+                .map(|_| "TALLY OK 0121\r\n".to_string())
+                .collect();
+            replies.push("ACTS OK InputPlaying 2 1\r\n".to_string());
+            replies.push("FUNCTION OK Completed\r\n".to_string());
+            replies
+        }
+        _ => vec![],
+    });
+    addr
+}
+
+/// Wakes the thread waiting in [`block_on`]
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future on the calling thread, without an async runtime
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+#[test]
+fn test_subscribers_receive_events_independently() {
+    let api = VmixApi::new(burst_server(2), Duration::from_secs(2)).unwrap();
+    let everything = api.subscribe(EventFilter::all(), 16);
+    let acts_only = api.subscribe(EventFilter::only(&[EventKind::Acts]), 16);
+
+    api.call_function("Sync", None, Duration::from_secs(2))
+        .unwrap();
+
+    let kinds: Vec<EventKind> = std::iter::from_fn(|| everything.try_recv().ok())
+        .map(|event| event.kind())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![EventKind::Tally, EventKind::Tally, EventKind::Acts]
+    );
+
+    let event = acts_only.try_recv().unwrap();
+    assert!(matches!(event, RecvCommand::ACTS(_)));
+    assert_eq!(acts_only.try_recv().unwrap_err(), RecvError::Timeout);
+}
+
+#[test]
+fn test_slow_subscriber_reports_lag() {
+    let api = VmixApi::new(burst_server(5), Duration::from_secs(2)).unwrap();
+    let tally = api.subscribe(EventFilter::only(&[EventKind::Tally]), 2);
+
+    api.call_function("Sync", None, Duration::from_secs(2))
+        .unwrap();
+
+    assert_eq!(tally.try_recv().unwrap_err(), RecvError::Lagged(3));
+    assert!(matches!(tally.try_recv().unwrap(), RecvCommand::TALLY(_)));
+    assert!(matches!(tally.try_recv().unwrap(), RecvCommand::TALLY(_)));
+    assert_eq!(tally.try_recv().unwrap_err(), RecvError::Timeout);
+}
+
+#[test]
+fn test_subscription_closes_with_connection() {
+    let (addr, server) = spawn_mock_vmix(|line| match line {
+        "QUIT" => vec!["QUIT OK Bye\r\n".to_string()],
+        _ => vec![],
+    });
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let events = api.subscribe(EventFilter::all(), 16);
+
+    api.disconnect().unwrap();
+    server.join().unwrap();
+
+    // The QUIT acknowledgement is delivered before the stream reports closure
+    assert!(matches!(
+        events.recv_timeout(Duration::from_secs(2)),
+        Ok(RecvCommand::QUIT)
+    ));
+    assert_eq!(
+        events.recv_timeout(Duration::from_secs(2)).unwrap_err(),
+        RecvError::Closed
+    );
+}
+
+#[test]
+fn test_builder_sends_startup_subscriptions() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "SUBSCRIBE TALLY" => vec![
+            "SUBSCRIBE OK TALLY\r\n".to_string(),
+            "TALLY OK 12\r\n".to_string(),
+        ],
+        "SUBSCRIBE ACTS" => vec!["SUBSCRIBE OK ACTS\r\n".to_string()],
+        _ => vec![],
+    });
+
+    let api = VmixApi::builder(addr)
+        .connect_timeout(Duration::from_secs(1))
+        .tcp_nodelay(true)
+        .tcp_keepalive(Some(Duration::from_secs(15)))
+        .event_buffer(8)
+        .subscribe(SUBSCRIBECommand::TALLY)
+        .subscribe(SUBSCRIBECommand::ACTS)
+        .build()
+        .unwrap();
+
+    let kinds: Vec<EventKind> = (0..3)
+        .map(|_| {
+            api.try_receive_command(Duration::from_secs(2))
+                .unwrap()
+                .kind()
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![EventKind::Subscribe, EventKind::Tally, EventKind::Subscribe]
+    );
+}
+
+#[test]
+fn test_subscription_is_awaited_without_a_runtime() {
+    let api = Arc::new(VmixApi::new(burst_server(1), Duration::from_secs(2)).unwrap());
+    let events = api.subscribe(EventFilter::only(&[EventKind::Acts]), 16);
+
+    let caller = api.clone();
+    let call = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        caller.call_function("Sync", None, Duration::from_secs(2))
+    });
+
+    // Nothing is buffered yet, so the future has to be woken by the reader thread
+    let event = block_on(events.recv_async()).unwrap();
+    assert!(matches!(event, RecvCommand::ACTS(_)));
+    call.join().unwrap().unwrap();
+
+    api.disconnect().unwrap();
+    assert_eq!(
+        block_on(events.recv_async()).unwrap_err(),
+        RecvError::Closed
+    );
+}
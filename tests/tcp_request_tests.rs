@@ -0,0 +1,165 @@
+mod common;
+
+use common::{spawn_mock_vmix, spawn_mock_vmix_with_greeting};
+use std::time::Duration;
+use vmix_rs::{
+    VmixError, commands::RecvCommand, models::version::VmixVersion, vmix::VmixApi,
+    vmix_tcp::EventFilter,
+};
+
+#[test]
+fn test_call_function_skips_interleaved_events() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "FUNCTION Cut" => vec![
+            "TALLY OK 0121\r\n".to_string(),
+            "ACTS OK Input 1 1\r\n".to_string(),
+            "FUNCTION OK Completed\r\n".to_string(),
+        ],
+        _ => vec![],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let response = api
+        .call_function("Cut", None, Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(response.body.as_deref(), Some("Completed"));
+
+    // The unsolicited events are still delivered to the event stream
+    let first = api.try_receive_command(Duration::from_secs(1)).unwrap();
+    assert!(matches!(first, RecvCommand::TALLY(_)));
+    let second = api.try_receive_command(Duration::from_secs(1)).unwrap();
+    assert!(matches!(second, RecvCommand::ACTS(_)));
+}
+
+#[test]
+fn test_call_function_reports_rejection() {
+    let (addr, _server) = spawn_mock_vmix(|line| {
+        if line.starts_with("FUNCTION") {
+            vec!["FUNCTION ER Input not found\r\n".to_string()]
+        } else {
+            vec![]
+        }
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let err = api
+        .call_function("PreviewInput", Some("Input=99"), Duration::from_secs(2))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        VmixError::VmixRejected { message } if message == "Input not found"
+    ));
+}
+
+#[test]
+fn test_responses_are_matched_in_fifo_order_per_type() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "XMLTEXT vmix/active" => vec!["XMLTEXT OK 1\r\n".to_string()],
+        "XMLTEXT vmix/inputs/input[1]/@title" => {
+            vec!["XMLTEXT OK Camera 1\r\n".to_string()]
+        }
+        "XML" => vec!["XML 21\r\n<vmix><x></x></vmix>\r\n".to_string()],
+        _ => vec![],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let timeout = Duration::from_secs(2);
+
+    let active = api.query_xmltext("vmix/active", timeout).unwrap();
+    assert_eq!(active.body.as_deref(), Some("1"));
+
+    let title = api
+        .query_xmltext("vmix/inputs/input[1]/@title", timeout)
+        .unwrap();
+    assert_eq!(title.body.as_deref(), Some("Camera 1"));
+
+    let xml = api.fetch_xml(timeout).unwrap();
+    assert_eq!(xml.body, "<vmix><x></x></vmix>");
+}
+
+#[test]
+fn test_request_times_out_individually() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        // Never answered
+        "XMLTEXT vmix/unknown" => vec![],
+        "FUNCTION Fade" => vec!["FUNCTION OK Completed\r\n".to_string()],
+        _ => vec![],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+
+    let err = api
+        .query_xmltext("vmix/unknown", Duration::from_millis(200))
+        .unwrap_err();
+    assert!(matches!(err, VmixError::Timeout(_)));
+
+    // Other command types are unaffected by the stuck request
+    let response = api
+        .call_function("Fade", None, Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(response.body.as_deref(), Some("Completed"));
+}
+
+#[test]
+fn test_late_response_is_not_an_event() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "XMLTEXT vmix/slow" => {
+            std::thread::sleep(Duration::from_millis(300));
+            vec!["XMLTEXT OK late\r\n".to_string()]
+        }
+        "XMLTEXT vmix/active" => vec!["XMLTEXT OK 1\r\n".to_string()],
+        _ => vec![],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let events = api.subscribe(EventFilter::all(), 16);
+
+    let err = api
+        .query_xmltext("vmix/slow", Duration::from_millis(100))
+        .unwrap_err();
+    assert!(matches!(err, VmixError::Timeout(_)));
+
+    // The late response is consumed, so the next request gets its own
+    let active = api
+        .query_xmltext("vmix/active", Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(active.body.as_deref(), Some("1"));
+
+    assert!(api.try_receive_command(Duration::from_millis(200)).is_err());
+    assert!(events.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_greeting_is_not_taken_for_version_response() {
+    // vMix greets the connection with its version before anything is asked
+    let (addr, _server) = spawn_mock_vmix_with_greeting(
+        "VERSION OK 27.0.0.81\r\n",
+        Duration::from_millis(100),
+        |line| match line {
+            "VERSION" => vec!["VERSION OK 28.0.0.1\r\n".to_string()],
+            _ => vec![],
+        },
+    );
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let version = api.version(Duration::from_secs(2)).unwrap();
+    assert_eq!(version, VmixVersion::new(28, 0, 0, 1));
+
+    // The greeting is not an event either
+    assert!(api.try_receive_command(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_connect_failure_is_reported_as_connect_error() {
+    // Bind and immediately drop a listener to get a port nobody is listening on
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let Err(err) = VmixApi::new(addr, Duration::from_secs(1)) else {
+        panic!("connecting to a closed port should fail");
+    };
+    assert!(matches!(err, VmixError::Connect { .. }));
+    assert!(std::error::Error::source(&err).is_some());
+}
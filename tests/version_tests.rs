@@ -0,0 +1,124 @@
+mod common;
+
+use common::spawn_mock_vmix;
+use std::time::Duration;
+use vmix_rs::{
+    VmixError,
+    models::{
+        Audiobusses,
+        functions::{InputRef, TransitionEffect, VmixFunction},
+        version::{Capabilities, VmixVersion},
+    },
+    vmix::VmixApi,
+};
+
+#[test]
+fn test_parse_version() {
+    let version: VmixVersion = "27.0.0.81".parse().unwrap();
+    assert_eq!(version, VmixVersion::new(27, 0, 0, 81));
+    assert_eq!(version.to_string(), "27.0.0.81");
+
+    // Missing parts default to 0
+    assert_eq!("26.1".parse(), Ok(VmixVersion::new(26, 1, 0, 0)));
+
+    for invalid in ["", "27", "27.x", "1.2.3.4.5", "27..1", "-1.0"] {
+        assert!(invalid.parse::<VmixVersion>().is_err(), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn test_versions_order_numerically() {
+    let mut versions: Vec<VmixVersion> = ["27.0.0.81", "27.0.0.9", "24.0.0.72", "27.1.0.0"]
+        .iter()
+        .map(|v| v.parse().unwrap())
+        .collect();
+    versions.sort();
+    let sorted: Vec<String> = versions.iter().map(ToString::to_string).collect();
+    assert_eq!(sorted, ["24.0.0.72", "27.0.0.9", "27.0.0.81", "27.1.0.0"]);
+}
+
+#[test]
+fn test_capabilities_by_version() {
+    let old = Capabilities::new(VmixVersion::major(21));
+    assert_eq!(old.mix_count(), 1);
+    assert!(old.has_mix(1));
+    assert!(!old.has_mix(2));
+
+    let current = Capabilities::new(VmixVersion::new(27, 0, 0, 81));
+    assert_eq!(current.mix_count(), 16);
+    assert!(current.has_mix(16));
+    assert!(!current.has_mix(17));
+    assert!(!current.has_mix(0));
+}
+
+#[test]
+fn test_capabilities_check_functions() {
+    let capabilities = Capabilities::new(VmixVersion::new(23, 0, 0, 31));
+
+    // Mix 4 exists, Mix 5 does not
+    let mix_4 = VmixFunction::ActiveInput {
+        input: InputRef::Number(1),
+        mix: Some(3),
+    };
+    assert!(capabilities.supports(&mix_4));
+    let mix_5 = VmixFunction::ActiveInput {
+        input: InputRef::Number(1),
+        mix: Some(4),
+    };
+    let err = capabilities.check(&mix_5).unwrap_err();
+    assert_eq!(err.feature, "Mix 5");
+    assert_eq!(err.required, VmixVersion::major(24));
+
+    assert!(capabilities.supports(&VmixFunction::cut()));
+
+    // Features whose first release is not confirmed are not checked
+    let old = Capabilities::new(VmixVersion::major(20));
+    assert!(old.supports(&VmixFunction::PtzHome {
+        input: InputRef::Number(1),
+    }));
+    assert!(old.supports(&VmixFunction::BusAudioOn {
+        bus: Audiobusses::F
+    }));
+    assert!(old.supports(&VmixFunction::Transition {
+        effect: TransitionEffect::Stinger(3),
+        input: None,
+        duration: None,
+        mix: None,
+    }));
+}
+
+#[test]
+fn test_unsupported_function_is_not_sent() {
+    let (addr, _server) = spawn_mock_vmix(|line| match line {
+        "VERSION" => vec!["VERSION OK 22.0.0.67\r\n".to_string()],
+        "FUNCTION Cut" => vec!["FUNCTION OK Completed\r\n".to_string()],
+        // Anything else reaching the server is a failure
+        _ => vec!["FUNCTION OK Unexpected\r\n".to_string()],
+    });
+
+    let api = VmixApi::new(addr, Duration::from_secs(2)).unwrap();
+    let timeout = Duration::from_secs(2);
+    assert_eq!(
+        api.version(timeout).unwrap(),
+        VmixVersion::new(22, 0, 0, 67)
+    );
+    assert!(api.capabilities().is_none());
+
+    let capabilities = api.detect_capabilities(timeout).unwrap();
+    assert_eq!(capabilities.mix_count(), 1);
+    assert_eq!(api.capabilities(), Some(capabilities));
+
+    let err = api
+        .execute(
+            &VmixFunction::PreviewInput {
+                input: InputRef::Number(2),
+                mix: Some(1),
+            },
+            timeout,
+        )
+        .unwrap_err();
+    assert!(matches!(err, VmixError::Unsupported(ref e) if e.feature == "Mix 2"));
+
+    let response = api.execute(&VmixFunction::cut(), timeout).unwrap();
+    assert_eq!(response.body.as_deref(), Some("Completed"));
+}
@@ -0,0 +1,199 @@
+use crate::models::{AudioBus, Audiobusses, Vmix};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// A single difference between two vMix states
+///
+/// Inputs are identified by their `key`, which stays the same when inputs are
+/// renumbered. Mix 1 is the main output.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum StateChange {
+    /// A different input is live on `mix`
+    Active {
+        mix: u8,
+        input: String,
+    },
+    /// A different input is in preview on `mix`
+    Preview {
+        mix: u8,
+        input: String,
+    },
+    InputAdded {
+        key: String,
+        number: String,
+    },
+    InputRemoved {
+        key: String,
+        number: String,
+    },
+    /// Any property of the input changed, including its number
+    InputChanged {
+        key: String,
+        number: String,
+    },
+    /// The input shown on overlay channel `number` changed
+    Overlay {
+        number: String,
+        input: Option<String>,
+    },
+    FadeToBlack(bool),
+    Recording(bool),
+    External(bool),
+    Streaming(bool),
+    PlayList(bool),
+    MultiCorder(bool),
+    Fullscreen(bool),
+    /// Volume, mute or solo of the master bus or an audio bus changed
+    Audio {
+        bus: Audiobusses,
+    },
+    Transitions,
+    Outputs,
+    Dynamic,
+}
+
+fn mixes(vmix: &Vmix) -> Vec<(u8, &str, &str)> {
+    let mut mixes = Vec::with_capacity(vmix.mix.len() + 1);
+    mixes.push((1, vmix.active.as_str(), vmix.preview.as_str()));
+    for mix in &vmix.mix {
+        if let Ok(number) = mix.number.trim().parse::<u8>() {
+            mixes.push((number, mix.active.as_str(), mix.preview.as_str()));
+        }
+    }
+    mixes
+}
+
+fn audio_busses(vmix: &Vmix) -> [(Audiobusses, Option<&AudioBus>); 8] {
+    let audio = &vmix.audio;
+    [
+        (Audiobusses::M, Some(&audio.master)),
+        (Audiobusses::A, audio.bus_a.as_ref()),
+        (Audiobusses::B, audio.bus_b.as_ref()),
+        (Audiobusses::C, audio.bus_c.as_ref()),
+        (Audiobusses::D, audio.bus_d.as_ref()),
+        (Audiobusses::E, audio.bus_e.as_ref()),
+        (Audiobusses::F, audio.bus_f.as_ref()),
+        (Audiobusses::G, audio.bus_g.as_ref()),
+    ]
+}
+
+/// Whether two buses differ in anything but their level meters
+fn audio_bus_changed(old: Option<&AudioBus>, new: Option<&AudioBus>) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            old.volume != new.volume
+                || old.muted != new.muted
+                || old.headphones_volume != new.headphones_volume
+                || old.solo != new.solo
+                || old.send_to_master != new.send_to_master
+        }
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// List the changes that turn `old` into `new`
+///
+/// Audio level meters change constantly and are ignored.
+pub fn diff(old: &Vmix, new: &Vmix) -> Vec<StateChange> {
+    let mut changes = Vec::new();
+
+    let old_mixes = mixes(old);
+    for (mix, active, preview) in mixes(new) {
+        let previous = old_mixes.iter().find(|(number, _, _)| *number == mix);
+        if previous.is_none_or(|(_, old_active, _)| *old_active != active) {
+            changes.push(StateChange::Active {
+                mix,
+                input: String::from(active),
+            });
+        }
+        if previous.is_none_or(|(_, _, old_preview)| *old_preview != preview) {
+            changes.push(StateChange::Preview {
+                mix,
+                input: String::from(preview),
+            });
+        }
+    }
+
+    for input in &new.inputs.input {
+        match old.inputs.input.iter().find(|i| i.key == input.key) {
+            None => changes.push(StateChange::InputAdded {
+                key: input.key.clone(),
+                number: input.number.clone(),
+            }),
+            Some(previous) => {
+                let mut previous = previous.clone();
+                // Meters are not state
+                previous.meter_f1 = input.meter_f1;
+                previous.meter_f2 = input.meter_f2;
+                if previous != *input {
+                    changes.push(StateChange::InputChanged {
+                        key: input.key.clone(),
+                        number: input.number.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for input in &old.inputs.input {
+        if !new.inputs.input.iter().any(|i| i.key == input.key) {
+            changes.push(StateChange::InputRemoved {
+                key: input.key.clone(),
+                number: input.number.clone(),
+            });
+        }
+    }
+
+    for overlay in &new.overlays.overlay {
+        let previous = old
+            .overlays
+            .overlay
+            .iter()
+            .find(|o| o.number == overlay.number);
+        if previous.is_none_or(|o| o.input != overlay.input) {
+            changes.push(StateChange::Overlay {
+                number: overlay.number.clone(),
+                input: overlay.input.clone(),
+            });
+        }
+    }
+
+    let flags = [
+        (
+            old.fade_to_black,
+            new.fade_to_black,
+            StateChange::FadeToBlack as fn(bool) -> StateChange,
+        ),
+        (old.recording, new.recording, StateChange::Recording),
+        (old.external, new.external, StateChange::External),
+        (old.streaming, new.streaming, StateChange::Streaming),
+        (old.play_list, new.play_list, StateChange::PlayList),
+        (old.multi_corder, new.multi_corder, StateChange::MultiCorder),
+        (old.fullscreen, new.fullscreen, StateChange::Fullscreen),
+    ];
+    for (old_value, new_value, change) in flags {
+        if old_value != new_value {
+            changes.push(change(new_value));
+        }
+    }
+
+    for ((bus, old_bus), (_, new_bus)) in audio_busses(old).into_iter().zip(audio_busses(new)) {
+        if audio_bus_changed(old_bus, new_bus) {
+            changes.push(StateChange::Audio { bus });
+        }
+    }
+
+    if old.transitions != new.transitions {
+        changes.push(StateChange::Transitions);
+    }
+    if old.outputs != new.outputs {
+        changes.push(StateChange::Outputs);
+    }
+    if old.dynamic != new.dynamic {
+        changes.push(StateChange::Dynamic);
+    }
+
+    changes
+}
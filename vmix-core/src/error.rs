@@ -0,0 +1,175 @@
+use crate::{query::UnknownParam, version::Unsupported};
+use std::fmt::{self, Display};
+
+/// Result type used by the vMix clients
+pub type Result<T, E = VmixError> = core::result::Result<T, E>;
+
+/// Errors returned by the vMix TCP and HTTP clients
+///
+/// Match on the variant to react to a particular kind of failure instead of
+/// inspecting the error message.
+///
+/// # Example
+/// ```rust,ignore
+/// match client.try_receive_command(Duration::from_millis(100)) {
+///     Ok(event) => println!("{:?}", event),
+///     Err(VmixError::Timeout(_)) => {} // nothing happened yet
+///     Err(VmixError::Disconnected) => break,
+///     Err(e) => return Err(e),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum VmixError {
+    /// The connection to vMix could not be established
+    Connect {
+        addr: String,
+        source: std::io::Error,
+    },
+    /// The operation did not complete in time
+    Timeout(String),
+    /// The connection to vMix is closed
+    Disconnected,
+    /// An I/O error occurred on an established connection
+    Io(std::io::Error),
+    /// vMix sent data that does not follow the protocol
+    Protocol(String),
+    /// vMix received the request but answered with an error
+    VmixRejected { message: String },
+    /// The HTTP API answered with a non-success status code
+    ///
+    /// `body` is the text vMix sent with it, which usually says what went
+    /// wrong; it may be empty.
+    HttpStatus { status: u16, body: String },
+    /// vMix failed repeatedly, so requests are not sent until `retry_after` has passed
+    CircuitOpen { retry_after: std::time::Duration },
+    /// The HTTP API is password protected and the credentials are missing or wrong
+    Unauthorized,
+    /// The HTTP response body is larger than the client accepts
+    BodyTooLarge { limit: usize },
+    /// The connected vMix version does not support the request, which was not sent
+    Unsupported(Unsupported),
+    /// A function parameter name is not one vMix knows; the request was not sent
+    UnknownParam(UnknownParam),
+    /// The transport cannot perform the operation, e.g. event subscriptions over HTTP
+    TransportUnsupported { operation: &'static str },
+    /// A response was not valid UTF-8
+    Utf8(std::string::FromUtf8Error),
+    /// The XML state could not be parsed
+    #[cfg(feature = "xml")]
+    XmlParse(quick_xml::DeError),
+}
+
+impl VmixError {
+    /// Whether the error was caused by an operation taking too long
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Self::Timeout(_) => true,
+            Self::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the connection to vMix is gone
+    pub fn is_disconnected(&self) -> bool {
+        match self {
+            Self::Disconnected => true,
+            Self::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::NotConnected
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl Display for VmixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect { addr, source } => {
+                write!(f, "failed to connect to {}: {}", addr, source)
+            }
+            Self::Timeout(operation) => write!(f, "timed out: {}", operation),
+            Self::Disconnected => write!(f, "connection to vMix closed"),
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Protocol(message) => write!(f, "protocol error: {}", message),
+            Self::VmixRejected { message } => write!(f, "vMix rejected the request: {}", message),
+            Self::HttpStatus { status, body } if body.is_empty() => {
+                write!(f, "HTTP request failed with status: {}", status)
+            }
+            Self::HttpStatus { status, body } => {
+                write!(f, "HTTP request failed with status: {}: {}", status, body)
+            }
+            Self::CircuitOpen { retry_after } => write!(
+                f,
+                "vMix is failing, requests are suspended for {:?}",
+                retry_after
+            ),
+            Self::Unauthorized => write!(f, "vMix rejected the credentials (HTTP 401)"),
+            Self::BodyTooLarge { limit } => {
+                write!(f, "HTTP response body exceeds {} bytes", limit)
+            }
+            Self::Unsupported(e) => write!(f, "unsupported: {}", e),
+            Self::UnknownParam(e) => write!(f, "{}", e),
+            Self::TransportUnsupported { operation } => {
+                write!(f, "{} is not supported by this transport", operation)
+            }
+            Self::Utf8(e) => write!(f, "response is not valid UTF-8: {}", e),
+            #[cfg(feature = "xml")]
+            Self::XmlParse(e) => write!(f, "failed to parse XML: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for VmixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Connect { source, .. } => Some(source),
+            Self::Io(e) => Some(e),
+            Self::Unsupported(e) => Some(e),
+            Self::UnknownParam(e) => Some(e),
+            Self::Utf8(e) => Some(e),
+            #[cfg(feature = "xml")]
+            Self::XmlParse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for VmixError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<Unsupported> for VmixError {
+    fn from(e: Unsupported) -> Self {
+        Self::Unsupported(e)
+    }
+}
+
+impl From<UnknownParam> for VmixError {
+    fn from(e: UnknownParam) -> Self {
+        Self::UnknownParam(e)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for VmixError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        Self::Utf8(e)
+    }
+}
+
+#[cfg(feature = "xml")]
+impl From<quick_xml::DeError> for VmixError {
+    fn from(e: quick_xml::DeError) -> Self {
+        Self::XmlParse(e)
+    }
+}
@@ -0,0 +1,762 @@
+use crate::{models::Audiobusses, query::FunctionParams};
+use core::fmt::{self, Display};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Input addressed by a function, by number, title or key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputRef {
+    Number(u16),
+    Name(String),
+    Key(String),
+}
+
+impl Display for InputRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Key(key) => write!(f, "{}", key),
+        }
+    }
+}
+
+impl From<u16> for InputRef {
+    fn from(number: u16) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<&str> for InputRef {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for InputRef {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+/// Field of a title input, by name (e.g. `Headline.Text`) or index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TitleField {
+    Name(String),
+    Index(u32),
+}
+
+impl From<&str> for TitleField {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<u32> for TitleField {
+    fn from(index: u32) -> Self {
+        Self::Index(index)
+    }
+}
+
+/// Transition effect, as used by the transition functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    Cut,
+    Fade,
+    Zoom,
+    Wipe,
+    Slide,
+    Fly,
+    CrossZoom,
+    FlyRotate,
+    Cube,
+    CubeZoom,
+    VerticalWipe,
+    VerticalSlide,
+    Merge,
+    WipeReverse,
+    SlideReverse,
+    VerticalWipeReverse,
+    VerticalSlideReverse,
+    /// `Stinger1` to `Stinger4`
+    Stinger(u8),
+}
+
+impl Display for TransitionEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stinger(number) => write!(f, "Stinger{}", number),
+            effect => write!(f, "{:?}", effect),
+        }
+    }
+}
+
+/// Direction of a PTZ move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PtzDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+fn bus_letter(bus: Audiobusses) -> &'static str {
+    match bus {
+        Audiobusses::M => "M",
+        Audiobusses::A => "A",
+        Audiobusses::B => "B",
+        Audiobusses::C => "C",
+        Audiobusses::D => "D",
+        Audiobusses::E => "E",
+        Audiobusses::F => "F",
+        Audiobusses::G => "G",
+    }
+}
+
+/// A vMix shortcut function with typed parameters
+///
+/// Covers the functions of the vMix shortcut function reference that are
+/// commonly automated. Anything else can be sent with [`VmixFunction::Custom`].
+/// The same function encodes to the same query string for TCP `FUNCTION`
+/// commands and HTTP `/api` requests.
+///
+/// # Example
+/// ```rust
+/// use vmix_core::functions::{TitleField, VmixFunction};
+///
+/// let function = VmixFunction::SetText {
+///     input: 3.into(),
+///     field: TitleField::from("Headline.Text"),
+///     value: "Hello world".to_string(),
+/// };
+/// assert_eq!(function.name(), "SetText");
+/// assert_eq!(
+///     function.query_string(),
+///     "Input=3&SelectedName=Headline.Text&Value=Hello%20world"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum VmixFunction {
+    // Transitions
+    /// Transition `input` (or the preview) to the output of `mix`
+    ///
+    /// `mix` is the vMix `Mix` parameter, which counts from 0: `None` or
+    /// `Some(0)` is the main output, `Some(1)` is Mix 2.
+    Transition {
+        effect: TransitionEffect,
+        input: Option<InputRef>,
+        duration: Option<u32>,
+        mix: Option<u8>,
+    },
+    /// One of the four transition buttons, `Transition1` to `Transition4`
+    TransitionButton {
+        number: u8,
+    },
+    CutDirect {
+        input: InputRef,
+    },
+    QuickPlay {
+        input: InputRef,
+    },
+    FadeToBlack,
+    PreviewInput {
+        input: InputRef,
+        mix: Option<u8>,
+    },
+    ActiveInput {
+        input: InputRef,
+        mix: Option<u8>,
+    },
+    PreviewInputNext,
+    PreviewInputPrevious,
+
+    // Overlays
+    /// Toggle `input` on overlay channel `overlay`
+    OverlayInput {
+        overlay: u8,
+        input: InputRef,
+    },
+    OverlayInputIn {
+        overlay: u8,
+        input: InputRef,
+    },
+    OverlayInputOut {
+        overlay: u8,
+    },
+    OverlayInputOff {
+        overlay: u8,
+    },
+    OverlayInputAllOff,
+
+    // Titles
+    SetText {
+        input: InputRef,
+        field: TitleField,
+        value: String,
+    },
+    SetTextColour {
+        input: InputRef,
+        field: TitleField,
+        value: String,
+    },
+    SetTextVisibleOn {
+        input: InputRef,
+        field: TitleField,
+    },
+    SetTextVisibleOff {
+        input: InputRef,
+        field: TitleField,
+    },
+    SetImage {
+        input: InputRef,
+        field: TitleField,
+        value: String,
+    },
+    TitleBeginAnimation {
+        input: InputRef,
+        animation: String,
+    },
+    SetCountdown {
+        input: InputRef,
+        field: TitleField,
+        value: String,
+    },
+    StartCountdown {
+        input: InputRef,
+        field: TitleField,
+    },
+    StopCountdown {
+        input: InputRef,
+        field: TitleField,
+    },
+    PauseCountdown {
+        input: InputRef,
+        field: TitleField,
+    },
+
+    // Audio
+    /// Set the volume of `input`, 0 to 100
+    SetVolume {
+        input: InputRef,
+        volume: u8,
+    },
+    /// Fade the volume of `input` to `volume` over `duration` milliseconds
+    SetVolumeFade {
+        input: InputRef,
+        volume: u8,
+        duration: u32,
+    },
+    SetBalance {
+        input: InputRef,
+        balance: f32,
+    },
+    /// Set the gain of `input` in dB, 0 to 24
+    SetGain {
+        input: InputRef,
+        gain: u8,
+    },
+    AudioOn {
+        input: InputRef,
+    },
+    AudioOff {
+        input: InputRef,
+    },
+    AudioBusOn {
+        input: InputRef,
+        bus: Audiobusses,
+    },
+    AudioBusOff {
+        input: InputRef,
+        bus: Audiobusses,
+    },
+    SoloOn {
+        input: InputRef,
+    },
+    SoloOff {
+        input: InputRef,
+    },
+    /// Set the volume of the master bus or an audio bus, 0 to 100
+    SetBusVolume {
+        bus: Audiobusses,
+        volume: u8,
+    },
+    BusAudioOn {
+        bus: Audiobusses,
+    },
+    BusAudioOff {
+        bus: Audiobusses,
+    },
+
+    // Replay
+    ReplayMarkIn,
+    ReplayMarkOut,
+    /// Mark an event covering the last `seconds`
+    ReplayMarkInOut {
+        seconds: u32,
+    },
+    ReplayPlay,
+    ReplayPause,
+    ReplayPlayLastEvent,
+    ReplayLiveToggle,
+    ReplayJumpToNow,
+    ReplaySetSpeed {
+        speed: f32,
+    },
+    /// Show camera `camera` (1 to 8) on the A channel
+    ReplayACamera {
+        camera: u8,
+    },
+    /// Show camera `camera` (1 to 8) on the B channel
+    ReplayBCamera {
+        camera: u8,
+    },
+
+    // PTZ
+    PtzMove {
+        input: InputRef,
+        direction: PtzDirection,
+        speed: Option<f32>,
+    },
+    PtzMoveStop {
+        input: InputRef,
+    },
+    PtzZoomIn {
+        input: InputRef,
+        speed: Option<f32>,
+    },
+    PtzZoomOut {
+        input: InputRef,
+        speed: Option<f32>,
+    },
+    PtzZoomStop {
+        input: InputRef,
+    },
+    PtzFocusAuto {
+        input: InputRef,
+    },
+    PtzFocusManual {
+        input: InputRef,
+    },
+    PtzHome {
+        input: InputRef,
+    },
+    PtzMoveToVirtualInputPosition {
+        input: InputRef,
+    },
+
+    // Lists and playback
+    NextItem {
+        input: InputRef,
+    },
+    PreviousItem {
+        input: InputRef,
+    },
+    SelectIndex {
+        input: InputRef,
+        index: u32,
+    },
+    ListShuffle {
+        input: InputRef,
+    },
+    ListAdd {
+        input: InputRef,
+        path: String,
+    },
+    ListRemove {
+        input: InputRef,
+        index: u32,
+    },
+    ListRemoveAll {
+        input: InputRef,
+    },
+    Play {
+        input: InputRef,
+    },
+    Pause {
+        input: InputRef,
+    },
+    PlayPause {
+        input: InputRef,
+    },
+    Restart {
+        input: InputRef,
+    },
+    LoopOn {
+        input: InputRef,
+    },
+    LoopOff {
+        input: InputRef,
+    },
+    /// Seek `input` to `position` milliseconds
+    SetPosition {
+        input: InputRef,
+        position: u32,
+    },
+
+    // Outputs
+    /// Route `source` (e.g. `Output`, `Preview`, `MultiView`, `Input`) to output 2 to 4
+    SetOutput {
+        output: u8,
+        source: String,
+        input: Option<InputRef>,
+    },
+    StartExternal,
+    StopExternal,
+    StartMultiCorder,
+    StopMultiCorder,
+    StartPlayList,
+    StopPlayList,
+    FullscreenOn,
+    FullscreenOff,
+
+    // Recording and streaming
+    StartRecording,
+    StopRecording,
+    StartStopRecording,
+    /// Start all streams, or only stream `channel` (0 to 2)
+    StartStreaming {
+        channel: Option<u8>,
+    },
+    StopStreaming {
+        channel: Option<u8>,
+    },
+    StartStopStreaming {
+        channel: Option<u8>,
+    },
+    /// Save a snapshot of the output, to `path` if given
+    Snapshot {
+        path: Option<String>,
+    },
+    SnapshotInput {
+        input: InputRef,
+        path: Option<String>,
+    },
+
+    /// Any other function, with its parameters in order
+    Custom {
+        name: String,
+        params: Vec<(String, String)>,
+    },
+}
+
+/// Name and parameters of an encoded function
+struct Call {
+    name: String,
+    params: FunctionParams,
+}
+
+impl Call {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: FunctionParams::new(),
+        }
+    }
+
+    fn param(mut self, key: &str, value: impl Display) -> Self {
+        self.params.push(key, value);
+        self
+    }
+
+    fn optional(self, key: &str, value: Option<impl Display>) -> Self {
+        match value {
+            Some(value) => self.param(key, value),
+            None => self,
+        }
+    }
+
+    fn input(self, input: &InputRef) -> Self {
+        self.param("Input", input)
+    }
+
+    fn field(self, field: &TitleField) -> Self {
+        match field {
+            TitleField::Name(name) => self.param("SelectedName", name),
+            TitleField::Index(index) => self.param("SelectedIndex", index),
+        }
+    }
+}
+
+impl VmixFunction {
+    /// Cut to the preview input
+    pub fn cut() -> Self {
+        Self::Transition {
+            effect: TransitionEffect::Cut,
+            input: None,
+            duration: None,
+            mix: None,
+        }
+    }
+
+    /// Fade to the preview input, over `duration` milliseconds if given
+    pub fn fade(duration: Option<u32>) -> Self {
+        Self::Transition {
+            effect: TransitionEffect::Fade,
+            input: None,
+            duration,
+            mix: None,
+        }
+    }
+
+    /// Name of the function, e.g. `OverlayInput1In`
+    pub fn name(&self) -> String {
+        self.call().name
+    }
+
+    /// Parameters of the function in the order they are sent
+    pub fn params(&self) -> FunctionParams {
+        self.call().params
+    }
+
+    /// Percent-encoded parameters, e.g. `Input=1&Duration=500`
+    ///
+    /// This is the query of a TCP `FUNCTION` command, and the part of an HTTP
+    /// `/api` request that follows `Function=<name>`.
+    pub fn query_string(&self) -> String {
+        self.call().params.to_query_string()
+    }
+
+    fn call(&self) -> Call {
+        match self {
+            Self::Transition {
+                effect,
+                input,
+                duration,
+                mix,
+            } => Call::new(effect.to_string())
+                .optional("Input", input.as_ref())
+                .optional("Duration", *duration)
+                .optional("Mix", *mix),
+            Self::TransitionButton { number } => Call::new(format!("Transition{}", number)),
+            Self::CutDirect { input } => Call::new("CutDirect").input(input),
+            Self::QuickPlay { input } => Call::new("QuickPlay").input(input),
+            Self::FadeToBlack => Call::new("FadeToBlack"),
+            Self::PreviewInput { input, mix } => {
+                Call::new("PreviewInput").input(input).optional("Mix", *mix)
+            }
+            Self::ActiveInput { input, mix } => {
+                Call::new("ActiveInput").input(input).optional("Mix", *mix)
+            }
+            Self::PreviewInputNext => Call::new("PreviewInputNext"),
+            Self::PreviewInputPrevious => Call::new("PreviewInputPrevious"),
+
+            Self::OverlayInput { overlay, input } => {
+                Call::new(format!("OverlayInput{}", overlay)).input(input)
+            }
+            Self::OverlayInputIn { overlay, input } => {
+                Call::new(format!("OverlayInput{}In", overlay)).input(input)
+            }
+            Self::OverlayInputOut { overlay } => Call::new(format!("OverlayInput{}Out", overlay)),
+            Self::OverlayInputOff { overlay } => Call::new(format!("OverlayInput{}Off", overlay)),
+            Self::OverlayInputAllOff => Call::new("OverlayInputAllOff"),
+
+            Self::SetText {
+                input,
+                field,
+                value,
+            } => Call::new("SetText")
+                .input(input)
+                .field(field)
+                .param("Value", value),
+            Self::SetTextColour {
+                input,
+                field,
+                value,
+            } => Call::new("SetTextColour")
+                .input(input)
+                .field(field)
+                .param("Value", value),
+            Self::SetTextVisibleOn { input, field } => {
+                Call::new("SetTextVisibleOn").input(input).field(field)
+            }
+            Self::SetTextVisibleOff { input, field } => {
+                Call::new("SetTextVisibleOff").input(input).field(field)
+            }
+            Self::SetImage {
+                input,
+                field,
+                value,
+            } => Call::new("SetImage")
+                .input(input)
+                .field(field)
+                .param("Value", value),
+            Self::TitleBeginAnimation { input, animation } => Call::new("TitleBeginAnimation")
+                .input(input)
+                .param("Value", animation),
+            Self::SetCountdown {
+                input,
+                field,
+                value,
+            } => Call::new("SetCountdown")
+                .input(input)
+                .field(field)
+                .param("Value", value),
+            Self::StartCountdown { input, field } => {
+                Call::new("StartCountdown").input(input).field(field)
+            }
+            Self::StopCountdown { input, field } => {
+                Call::new("StopCountdown").input(input).field(field)
+            }
+            Self::PauseCountdown { input, field } => {
+                Call::new("PauseCountdown").input(input).field(field)
+            }
+
+            Self::SetVolume { input, volume } => {
+                Call::new("SetVolume").input(input).param("Value", volume)
+            }
+            Self::SetVolumeFade {
+                input,
+                volume,
+                duration,
+            } => Call::new("SetVolumeFade")
+                .input(input)
+                .param("Value", format!("{},{}", volume, duration)),
+            Self::SetBalance { input, balance } => {
+                Call::new("SetBalance").input(input).param("Value", balance)
+            }
+            Self::SetGain { input, gain } => Call::new("SetGain").input(input).param("Value", gain),
+            Self::AudioOn { input } => Call::new("AudioOn").input(input),
+            Self::AudioOff { input } => Call::new("AudioOff").input(input),
+            Self::AudioBusOn { input, bus } => Call::new("AudioBusOn")
+                .input(input)
+                .param("Value", bus_letter(*bus)),
+            Self::AudioBusOff { input, bus } => Call::new("AudioBusOff")
+                .input(input)
+                .param("Value", bus_letter(*bus)),
+            Self::SoloOn { input } => Call::new("SoloOn").input(input),
+            Self::SoloOff { input } => Call::new("SoloOff").input(input),
+            Self::SetBusVolume {
+                bus: Audiobusses::M,
+                volume,
+            } => Call::new("SetMasterVolume").param("Value", volume),
+            Self::SetBusVolume { bus, volume } => {
+                Call::new(format!("SetBus{}Volume", bus_letter(*bus))).param("Value", volume)
+            }
+            Self::BusAudioOn {
+                bus: Audiobusses::M,
+            } => Call::new("MasterAudioON"),
+            Self::BusAudioOn { bus } => Call::new("BusXAudioOn").param("Value", bus_letter(*bus)),
+            Self::BusAudioOff {
+                bus: Audiobusses::M,
+            } => Call::new("MasterAudioOFF"),
+            Self::BusAudioOff { bus } => Call::new("BusXAudioOff").param("Value", bus_letter(*bus)),
+
+            Self::ReplayMarkIn => Call::new("ReplayMarkIn"),
+            Self::ReplayMarkOut => Call::new("ReplayMarkOut"),
+            Self::ReplayMarkInOut { seconds } => {
+                Call::new("ReplayMarkInOut").param("Value", seconds)
+            }
+            Self::ReplayPlay => Call::new("ReplayPlay"),
+            Self::ReplayPause => Call::new("ReplayPause"),
+            Self::ReplayPlayLastEvent => Call::new("ReplayPlayLastEvent"),
+            Self::ReplayLiveToggle => Call::new("ReplayLiveToggle"),
+            Self::ReplayJumpToNow => Call::new("ReplayJumpToNow"),
+            Self::ReplaySetSpeed { speed } => Call::new("ReplaySetSpeed").param("Value", speed),
+            Self::ReplayACamera { camera } => Call::new(format!("ReplayACamera{}", camera)),
+            Self::ReplayBCamera { camera } => Call::new(format!("ReplayBCamera{}", camera)),
+
+            Self::PtzMove {
+                input,
+                direction,
+                speed,
+            } => Call::new(format!("PTZMove{:?}", direction))
+                .input(input)
+                .optional("Value", *speed),
+            Self::PtzMoveStop { input } => Call::new("PTZMoveStop").input(input),
+            Self::PtzZoomIn { input, speed } => Call::new("PTZZoomIn")
+                .input(input)
+                .optional("Value", *speed),
+            Self::PtzZoomOut { input, speed } => Call::new("PTZZoomOut")
+                .input(input)
+                .optional("Value", *speed),
+            Self::PtzZoomStop { input } => Call::new("PTZZoomStop").input(input),
+            Self::PtzFocusAuto { input } => Call::new("PTZFocusAuto").input(input),
+            Self::PtzFocusManual { input } => Call::new("PTZFocusManual").input(input),
+            Self::PtzHome { input } => Call::new("PTZHome").input(input),
+            Self::PtzMoveToVirtualInputPosition { input } => {
+                Call::new("PTZMoveToVirtualInputPosition").input(input)
+            }
+
+            Self::NextItem { input } => Call::new("NextItem").input(input),
+            Self::PreviousItem { input } => Call::new("PreviousItem").input(input),
+            Self::SelectIndex { input, index } => {
+                Call::new("SelectIndex").input(input).param("Value", index)
+            }
+            Self::ListShuffle { input } => Call::new("ListShuffle").input(input),
+            Self::ListAdd { input, path } => Call::new("ListAdd").input(input).param("Value", path),
+            Self::ListRemove { input, index } => {
+                Call::new("ListRemove").input(input).param("Value", index)
+            }
+            Self::ListRemoveAll { input } => Call::new("ListRemoveAll").input(input),
+            Self::Play { input } => Call::new("Play").input(input),
+            Self::Pause { input } => Call::new("Pause").input(input),
+            Self::PlayPause { input } => Call::new("PlayPause").input(input),
+            Self::Restart { input } => Call::new("Restart").input(input),
+            Self::LoopOn { input } => Call::new("LoopOn").input(input),
+            Self::LoopOff { input } => Call::new("LoopOff").input(input),
+            Self::SetPosition { input, position } => Call::new("SetPosition")
+                .input(input)
+                .param("Value", position),
+
+            Self::SetOutput {
+                output,
+                source,
+                input,
+            } => Call::new(format!("SetOutput{}", output))
+                .param("Value", source)
+                .optional("Input", input.as_ref()),
+            Self::StartExternal => Call::new("StartExternal"),
+            Self::StopExternal => Call::new("StopExternal"),
+            Self::StartMultiCorder => Call::new("StartMultiCorder"),
+            Self::StopMultiCorder => Call::new("StopMultiCorder"),
+            Self::StartPlayList => Call::new("StartPlayList"),
+            Self::StopPlayList => Call::new("StopPlayList"),
+            Self::FullscreenOn => Call::new("FullscreenOn"),
+            Self::FullscreenOff => Call::new("FullscreenOff"),
+
+            Self::StartRecording => Call::new("StartRecording"),
+            Self::StopRecording => Call::new("StopRecording"),
+            Self::StartStopRecording => Call::new("StartStopRecording"),
+            Self::StartStreaming { channel } => {
+                Call::new("StartStreaming").optional("Value", *channel)
+            }
+            Self::StopStreaming { channel } => {
+                Call::new("StopStreaming").optional("Value", *channel)
+            }
+            Self::StartStopStreaming { channel } => {
+                Call::new("StartStopStreaming").optional("Value", *channel)
+            }
+            Self::Snapshot { path } => Call::new("Snapshot").optional("Value", path.as_ref()),
+            Self::SnapshotInput { input, path } => Call::new("SnapshotInput")
+                .input(input)
+                .optional("Value", path.as_ref()),
+
+            Self::Custom { name, params } => Call {
+                name: name.clone(),
+                params: params.clone().into(),
+            },
+        }
+    }
+}
+
+impl Display for VmixFunction {
+    /// The function as sent over TCP, e.g. `SetVolume Input=1&Value=50`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let query = self.query_string();
+        if query.is_empty() {
+            write!(f, "{}", self.name())
+        } else {
+            write!(f, "{} {}", self.name(), query)
+        }
+    }
+}
@@ -2,10 +2,22 @@
 
 extern crate alloc;
 
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod error;
+pub mod functions;
 pub mod models;
+pub mod query;
+pub mod version;
 
 // Re-export for convenience
+pub use diff::{StateChange, diff};
+#[cfg(feature = "std")]
+pub use error::VmixError;
+pub use functions::VmixFunction;
 pub use models::*;
+pub use query::{FunctionParams, UnknownParam};
+pub use version::{Capabilities, VmixVersion};
 
 // XML parsing features (optional)
 #[cfg(feature = "xml")]
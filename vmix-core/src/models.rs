@@ -347,6 +347,10 @@ pub struct Overlays {
 pub struct OverlaysOverlay {
     #[serde(rename = "@number")]
     pub number: String,
+
+    /// Number of the input shown on this overlay, if any
+    #[serde(rename = "$text", default)]
+    pub input: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -498,7 +502,7 @@ pub struct Crop {
     pub y2: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Audiobusses {
     #[serde(rename = "M")]
     M,
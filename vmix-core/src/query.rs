@@ -0,0 +1,267 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::fmt::{self, Display, Write};
+
+/// Percent-encode a query string key or value
+///
+/// Letters, digits and `-._~` are kept, every other byte of the UTF-8
+/// encoding becomes `%XX`. This matches the encoding used by the HTTP
+/// client, so TCP and HTTP requests carry the same query string.
+pub fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+    encoded
+}
+
+/// Decode a percent-encoded query string key or value
+///
+/// `+` is kept as is, as `encode` never produces it. Returns `None` for a
+/// truncated or invalid `%XX` escape, or when the decoded bytes are not UTF-8.
+pub fn decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let high = hex_digit(input.next()?)?;
+            let low = hex_digit(input.next()?)?;
+            bytes.push(high << 4 | low);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Names of the parameters vMix functions take, besides `Function` itself
+pub const PARAM_NAMES: &[&str] = &[
+    "Input",
+    "Value",
+    "Duration",
+    "SelectedName",
+    "SelectedIndex",
+    "Mix",
+];
+
+/// A parameter name no vMix function takes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownParam {
+    pub name: String,
+}
+
+impl Display for UnknownParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown function parameter {:?}, expected one of ",
+            self.name
+        )?;
+        for (index, name) in PARAM_NAMES.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for UnknownParam {}
+
+/// Ordered parameters of a vMix function
+///
+/// Keys and values are stored decoded and percent-encoded only when the
+/// query string is built, so values may contain spaces, `&`, `=` or any
+/// non-ASCII text.
+///
+/// # Example
+///
+/// ```
+/// use vmix_core::query::FunctionParams;
+///
+/// let params = FunctionParams::new()
+///     .with("Input", 1)
+///     .with("SelectedName", "Title.Text")
+///     .with("Value", "Q&A 1/2");
+/// assert_eq!(
+///     params.to_string(),
+///     "Input=1&SelectedName=Title.Text&Value=Q%26A%201%2F2"
+/// );
+/// assert_eq!(FunctionParams::parse(&params.to_string()), Some(params));
+/// ```
+///
+/// Parameters keep the order they were added in, and a key may be repeated,
+/// so the same parameters always produce the same query string.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionParams {
+    params: Vec<(String, String)>,
+}
+
+impl FunctionParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a parameter
+    pub fn with(mut self, key: impl Into<String>, value: impl Display) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    /// Append an `Input` parameter: a number, name or key
+    pub fn input(self, input: impl Display) -> Self {
+        self.with("Input", input)
+    }
+
+    /// Append a `Value` parameter
+    pub fn value(self, value: impl Display) -> Self {
+        self.with("Value", value)
+    }
+
+    /// Append a `Duration` parameter in milliseconds
+    pub fn duration(self, milliseconds: u32) -> Self {
+        self.with("Duration", milliseconds)
+    }
+
+    /// Append a `SelectedName` parameter, e.g. the name of a title field
+    pub fn selected_name(self, name: impl Display) -> Self {
+        self.with("SelectedName", name)
+    }
+
+    /// Append a `SelectedIndex` parameter
+    pub fn selected_index(self, index: u32) -> Self {
+        self.with("SelectedIndex", index)
+    }
+
+    /// Append a `Mix` parameter, 0 being the main output
+    pub fn mix(self, mix: u8) -> Self {
+        self.with("Mix", mix)
+    }
+
+    /// Append a parameter if `value` is `Some`
+    pub fn with_optional(self, key: impl Into<String>, value: Option<impl Display>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+
+    /// Append a parameter
+    pub fn push(&mut self, key: impl Into<String>, value: impl Display) {
+        self.params.push((key.into(), value.to_string()));
+    }
+
+    /// Value of the first parameter named `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Parameters in the order they are sent
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Check that every parameter is one vMix functions take
+    ///
+    /// Names are compared ignoring case, as vMix does.
+    pub fn validate(&self) -> Result<(), UnknownParam> {
+        match self.params.iter().find(|(key, _)| {
+            !PARAM_NAMES
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(key))
+        }) {
+            Some((key, _)) => Err(UnknownParam { name: key.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse a percent-encoded query string such as `Input=1&Value=Hello%20world`
+    ///
+    /// A parameter without `=` gets an empty value. Returns `None` if any key
+    /// or value fails to [`decode`].
+    pub fn parse(query: &str) -> Option<Self> {
+        let mut params = Self::new();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            params.params.push((decode(key)?, decode(value)?));
+        }
+        Some(params)
+    }
+
+    /// Percent-encoded query string, e.g. `Input=1&Duration=500`
+    pub fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for FunctionParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.params.iter().enumerate() {
+            if index > 0 {
+                f.write_char('&')?;
+            }
+            write!(f, "{}={}", encode(key), encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<(String, String)>> for FunctionParams {
+    fn from(params: Vec<(String, String)>) -> Self {
+        Self { params }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for FunctionParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            params: iter
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+impl IntoIterator for FunctionParams {
+    type Item = (String, String);
+    type IntoIter = alloc::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.params.into_iter()
+    }
+}
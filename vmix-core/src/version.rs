@@ -0,0 +1,212 @@
+use crate::{functions::VmixFunction, models::Vmix};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// A vMix version such as `27.0.0.81`
+///
+/// Versions order by major, minor, build and revision, so capability checks
+/// can compare against the release that introduced a feature.
+///
+/// # Example
+///
+/// ```
+/// use vmix_core::version::VmixVersion;
+///
+/// let version: VmixVersion = "27.0.0.81".parse().unwrap();
+/// assert_eq!(version.major, 27);
+/// assert!(version > VmixVersion::new(26, 0, 0, 0));
+/// assert_eq!(version.to_string(), "27.0.0.81");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VmixVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl VmixVersion {
+    pub const fn new(major: u16, minor: u16, build: u16, revision: u16) -> Self {
+        Self {
+            major,
+            minor,
+            build,
+            revision,
+        }
+    }
+
+    /// First release of major version `major`
+    pub const fn major(major: u16) -> Self {
+        Self::new(major, 0, 0, 0)
+    }
+}
+
+impl Display for VmixVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+/// Error returned when a version string is not `major.minor[.build[.revision]]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionError {
+    input: String,
+}
+
+impl Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid vMix version: {:?}", self.input)
+    }
+}
+
+impl core::error::Error for ParseVersionError {}
+
+impl FromStr for VmixVersion {
+    type Err = ParseVersionError;
+
+    /// Parse two to four dot separated numbers, missing parts are 0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseVersionError {
+            input: String::from(s),
+        };
+        let mut parts = [0u16; 4];
+        let mut count = 0;
+        for part in s.trim().split('.') {
+            let slot = parts.get_mut(count).ok_or_else(error)?;
+            *slot = part.parse().map_err(|_| error())?;
+            count += 1;
+        }
+        if count < 2 {
+            return Err(error());
+        }
+        let [major, minor, build, revision] = parts;
+        Ok(Self::new(major, minor, build, revision))
+    }
+}
+
+impl Vmix {
+    /// The `version` element parsed, or `None` if it is not a version number
+    pub fn parsed_version(&self) -> Option<VmixVersion> {
+        self.version.parse().ok()
+    }
+}
+
+/// First version with Mix 2 to Mix 4, added as Mix inputs in the vMix 23 release notes
+const MIXES_2_TO_4: VmixVersion = VmixVersion::major(23);
+/// First version with Mix 5 to Mix 16, raised from 4 mixes in the vMix 24 release notes
+const MIXES_5_TO_16: VmixVersion = VmixVersion::major(24);
+
+/// A feature the connected vMix does not have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// Function or feature that was requested, e.g. `Mix 5`
+    pub feature: String,
+    /// First version that has it
+    pub required: VmixVersion,
+    /// Version it was requested on
+    pub version: VmixVersion,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requires vMix {} or later, connected to {}",
+            self.feature, self.required, self.version
+        )
+    }
+}
+
+impl core::error::Error for Unsupported {}
+
+/// What a given vMix version supports
+///
+/// The TCP and HTTP clients consult this to reject calls before sending them.
+/// Only the number of mixes is checked: other features are assumed to exist
+/// in every version until the release that added them is confirmed.
+///
+/// # Example
+///
+/// ```
+/// use vmix_core::version::{Capabilities, VmixVersion};
+///
+/// let capabilities = Capabilities::new(VmixVersion::new(21, 0, 0, 55));
+/// assert_eq!(capabilities.mix_count(), 1);
+/// assert!(capabilities.has_mix(1));
+/// assert!(!capabilities.has_mix(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    version: VmixVersion,
+}
+
+impl Capabilities {
+    pub const fn new(version: VmixVersion) -> Self {
+        Self { version }
+    }
+
+    /// Capabilities of the version reported in an XML snapshot
+    pub fn from_snapshot(vmix: &Vmix) -> Option<Self> {
+        vmix.parsed_version().map(Self::new)
+    }
+
+    pub fn version(&self) -> VmixVersion {
+        self.version
+    }
+
+    /// Number of mixes, including the main output
+    pub fn mix_count(&self) -> u8 {
+        if self.version >= MIXES_5_TO_16 {
+            16
+        } else if self.version >= MIXES_2_TO_4 {
+            4
+        } else {
+            1
+        }
+    }
+
+    /// Whether mix `mix` exists, 1 being the main output
+    pub fn has_mix(&self, mix: u8) -> bool {
+        (1..=self.mix_count()).contains(&mix)
+    }
+
+    /// Whether `function` can be executed
+    pub fn supports(&self, function: &VmixFunction) -> bool {
+        self.check(function).is_ok()
+    }
+
+    /// Check that the mix `function` addresses exists
+    pub fn check(&self, function: &VmixFunction) -> Result<(), Unsupported> {
+        match function_mix(function).filter(|mix| !self.has_mix(*mix)) {
+            Some(mix) => Err(Unsupported {
+                feature: alloc::format!("Mix {}", mix),
+                required: if mix <= 4 {
+                    MIXES_2_TO_4
+                } else {
+                    MIXES_5_TO_16
+                },
+                version: self.version,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Mix addressed by a function, 1 being the main output
+fn function_mix(function: &VmixFunction) -> Option<u8> {
+    match function {
+        VmixFunction::Transition { mix, .. }
+        | VmixFunction::PreviewInput { mix, .. }
+        | VmixFunction::ActiveInput { mix, .. } => mix.map(|mix| mix.saturating_add(1)),
+        _ => None,
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{
+    auth::Credentials,
+    client::HttpVmixClient,
+    response::HttpResponse,
+    retry::{CircuitBreakerConfig, CircuitState, RetryPolicy},
+    transport::HttpTransport,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+use vmix_core::{Capabilities, FunctionParams, Vmix, VmixFunction, error::Result};
+use vmix_tcp::{InputNumber, TallyData};
+
+/// Client for the vMix HTTP API that blocks the calling thread
+///
+/// Has the methods of [`HttpVmixClient`] without needing an async runtime, for
+/// command-line tools and synchronous applications. Connections are kept open
+/// between requests and shared by the clones of a client, which can be used
+/// from several threads.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_http::BlockingHttpVmixClient;
+///
+/// let client = BlockingHttpVmixClient::new("127.0.0.1:8088".parse()?, Duration::from_secs(5));
+/// client.cut()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockingHttpVmixClient {
+    inner: HttpVmixClient<BlockingTransport>,
+}
+
+/// Builder methods of the async client
+macro_rules! builders {
+    ($($(#[$doc:meta])* $name:ident($($arg:ident: $ty:ty),*);)*) => {$(
+        $(#[$doc])*
+        pub fn $name(self, $($arg: $ty),*) -> Self {
+            Self {
+                inner: self.inner.$name($($arg),*),
+            }
+        }
+    )*};
+}
+
+/// Async methods of the async client, run to completion on the calling thread
+macro_rules! blocking {
+    ($($(#[$doc:meta])* $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {$(
+        $(#[$doc])*
+        pub fn $name(&self, $($arg: $ty),*) -> $ret {
+            block_on(self.inner.$name($($arg),*))
+        }
+    )*};
+}
+
+impl BlockingHttpVmixClient {
+    pub fn new(addr: SocketAddr, request_timeout: Duration) -> Self {
+        Self::new_with_host_port(&addr.ip().to_string(), addr.port(), request_timeout)
+    }
+
+    pub fn new_with_host_port(host: &str, port: u16, request_timeout: Duration) -> Self {
+        let transport = BlockingTransport { request_timeout };
+        Self {
+            inner: HttpVmixClient::new_with_transport(host, port, request_timeout, transport),
+        }
+    }
+
+    builders! {
+        /// Check functions against `capabilities` before sending them
+        with_capabilities(capabilities: Capabilities);
+        /// Authenticate to a password protected web controller with HTTP Basic authentication
+        ///
+        /// Requests with wrong credentials fail with [`VmixError::Unauthorized`](vmix_core::VmixError::Unauthorized).
+        with_basic_auth(username: &str, password: &str);
+        /// Authenticate with `credentials`, or not at all with `None`
+        with_credentials(credentials: Option<Credentials>);
+        /// Retry requests that failed because vMix could not be reached
+        ///
+        /// By default every request is attempted once. The calling thread sleeps
+        /// between attempts.
+        with_retry(retry: RetryPolicy);
+        /// Stop sending requests for a while after repeated failures
+        ///
+        /// The breaker is shared by the clones of the client made afterwards.
+        with_circuit_breaker(config: CircuitBreakerConfig);
+        /// Reuse connections between requests (enabled by default)
+        with_keep_alive(keep_alive: bool);
+        /// Keep at most `max_idle` open connections for reuse (4 by default)
+        with_max_idle_connections(max_idle: usize);
+        /// Close connections unused for longer than `idle_timeout` instead of reusing them (30s by default)
+        with_idle_timeout(idle_timeout: Duration);
+        /// Fail requests whose response body is larger than `max_body_size` bytes (64 MiB by default)
+        with_max_body_size(max_body_size: usize);
+    }
+
+    /// State of the circuit breaker, if there is one
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.inner.circuit_state()
+    }
+
+    /// Number of open connections waiting to be reused
+    pub fn idle_connections(&self) -> usize {
+        self.inner.idle_connections()
+    }
+
+    /// Close the connections waiting to be reused
+    pub fn close_idle_connections(&self) {
+        self.inner.close_idle_connections()
+    }
+
+    /// Capabilities used to check functions before they are sent, if known
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.inner.capabilities()
+    }
+
+    /// Read the vMix version from the XML state and remember its capabilities
+    pub fn detect_capabilities(&mut self) -> Result<Capabilities> {
+        block_on(self.inner.detect_capabilities())
+    }
+
+    blocking! {
+        /// Execute a vMix function
+        ///
+        /// The parameters are sent sorted by name; use
+        /// [`execute_function_with_params`](Self::execute_function_with_params)
+        /// to choose their order or repeat one.
+        execute_function(function: &str, params: &HashMap<String, String>) -> Result<()>;
+        /// Execute a vMix function and return the response to it
+        execute_function_with_response(function: &str, params: &HashMap<String, String>) -> Result<HttpResponse>;
+        /// Execute a vMix function with parameters sent in the given order
+        ///
+        /// Parameter names are checked first: a name vMix does not know fails with
+        /// [`VmixError::UnknownParam`](vmix_core::VmixError::UnknownParam) without being sent.
+        execute_function_with_params(function: &str, params: &FunctionParams) -> Result<HttpResponse>;
+        /// Execute a typed vMix function
+        ///
+        /// Once the capabilities are known, functions the connected vMix does not support
+        /// fail with [`VmixError::Unsupported`](vmix_core::VmixError::Unsupported) without being sent.
+        execute(function: &VmixFunction) -> Result<()>;
+        /// Execute a typed vMix function and return the response to it
+        execute_with_response(function: &VmixFunction) -> Result<HttpResponse>;
+        /// Query a single value from the vMix XML state using an XPath expression
+        xml_text(path: &str) -> Result<String>;
+        get_xml_state() -> Result<Vmix>;
+        get_tally_data() -> Result<HashMap<InputNumber, TallyData>>;
+        /// Tally of the input with `key`, from the endpoint used by vMix web tally lights
+        tally_for_key(key: &str) -> Result<TallyData>;
+        is_connected() -> bool;
+        get_active_input() -> Result<InputNumber>;
+        get_preview_input() -> Result<InputNumber>;
+    }
+
+    /// URL executing a vMix function, without credentials
+    pub fn function_url(&self, function: &str, params: &FunctionParams) -> Result<String> {
+        self.inner.function_url(function, params)
+    }
+
+    /// URL of the API, without credentials
+    pub fn get_base_url(&self) -> String {
+        self.inner.get_base_url()
+    }
+}
+
+// Helper function for common vMix functions
+impl BlockingHttpVmixClient {
+    blocking! {
+        cut() -> Result<()>;
+        fade(duration_ms: Option<u32>) -> Result<()>;
+        preview_input(input: InputNumber) -> Result<()>;
+        active_input(input: InputNumber) -> Result<()>;
+        set_text(input: InputNumber, selected_name: &str, value: &str) -> Result<()>;
+        start_recording() -> Result<()>;
+        stop_recording() -> Result<()>;
+        start_streaming() -> Result<()>;
+        stop_streaming() -> Result<()>;
+    }
+}
+
+/// Blocking `std` sockets, for the client run by [`block_on`]
+///
+/// Every operation is done when first polled, so the futures of the client
+/// complete in a single poll and its timeouts are the socket timeouts.
+#[derive(Debug, Clone)]
+pub(crate) struct BlockingTransport {
+    request_timeout: Duration,
+}
+
+impl HttpTransport for BlockingTransport {
+    type Connection = TcpStream;
+
+    async fn connect(&self, addr: &str) -> io::Result<TcpStream> {
+        let mut last_error = None;
+        for socket_addr in addr.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&socket_addr, self.request_timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(self.request_timeout))?;
+                    stream.set_write_timeout(Some(self.request_timeout))?;
+                    return Ok(stream);
+                }
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => return Err(e),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no address found")))
+    }
+
+    async fn read(&self, connection: &mut TcpStream, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match connection.read(buf) {
+                // A read timeout is reported as either kind, depending on the platform
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                result => return result,
+            }
+        }
+    }
+
+    async fn write_all(&self, connection: &mut TcpStream, buf: &[u8]) -> io::Result<()> {
+        connection.write_all(buf)?;
+        connection.flush()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+
+    fn is_open(&self, connection: &mut TcpStream) -> bool {
+        if connection.set_nonblocking(true).is_err() {
+            return false;
+        }
+        let mut byte = [0u8; 1];
+        let open =
+            matches!(connection.peek(&mut byte), Err(e) if e.kind() == io::ErrorKind::WouldBlock);
+        connection.set_nonblocking(false).is_ok() && open
+    }
+}
+
+/// Wakes the thread waiting in [`block_on`]
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future of the client on the calling thread
+///
+/// With [`BlockingTransport`] the future never waits; the thread is parked
+/// until it is woken if it does.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
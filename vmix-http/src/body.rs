@@ -0,0 +1,245 @@
+use vmix_core::error::{Result, VmixError};
+
+/// Longest chunk-size or trailer line accepted in a chunked body
+const MAX_LINE: usize = 4096;
+
+/// How the end of a response body is determined (RFC 9112, section 6.3)
+#[derive(Debug)]
+pub(crate) enum BodyDecoder {
+    /// Exactly `remaining` more bytes
+    Length { remaining: usize },
+    /// `Transfer-Encoding: chunked`
+    Chunked { state: ChunkState, line: Vec<u8> },
+    /// Everything until the server closes the connection
+    UntilClose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkState {
+    /// Reading a chunk-size line
+    Size,
+    /// Reading chunk data
+    Data {
+        remaining: usize,
+    },
+    /// Reading the CRLF after chunk data
+    DataEnd,
+    /// Reading trailer fields after the last chunk
+    Trailers,
+    Done,
+}
+
+impl BodyDecoder {
+    /// Framing of the body of a response with `status` and `headers`
+    pub(crate) fn new(status: u16, headers: &[(String, String)]) -> Result<Self> {
+        if status == 204 || status == 304 || (100..200).contains(&status) {
+            return Ok(Self::Length { remaining: 0 });
+        }
+
+        // Transfer-Encoding takes precedence over Content-Length
+        if let Some(coding) = header_values(headers, "transfer-encoding").last() {
+            return if coding.eq_ignore_ascii_case("chunked") {
+                Ok(Self::Chunked {
+                    state: ChunkState::Size,
+                    line: Vec::new(),
+                })
+            } else {
+                Ok(Self::UntilClose)
+            };
+        }
+
+        let mut lengths = header_values(headers, "content-length");
+        match lengths.next() {
+            None => Ok(Self::UntilClose),
+            Some(first) => {
+                if lengths.any(|other| other != first) {
+                    return Err(VmixError::Protocol(
+                        "conflicting Content-Length headers".to_string(),
+                    ));
+                }
+                let remaining = first.parse().map_err(|_| {
+                    VmixError::Protocol(format!("invalid Content-Length: {}", first))
+                })?;
+                Ok(Self::Length { remaining })
+            }
+        }
+    }
+
+    /// Decode the body bytes at the start of `input` into `body`
+    ///
+    /// Returns the number of bytes used. All of `input` is used unless the body
+    /// is complete; anything left over does not belong to this response.
+    pub(crate) fn decode(&mut self, input: &[u8], body: &mut Vec<u8>) -> Result<usize> {
+        match self {
+            Self::Length { remaining } => {
+                let n = input.len().min(*remaining);
+                body.extend_from_slice(&input[..n]);
+                *remaining -= n;
+                Ok(n)
+            }
+            Self::Chunked { state, line } => decode_chunked(state, line, input, body),
+            Self::UntilClose => {
+                body.extend_from_slice(input);
+                Ok(input.len())
+            }
+        }
+    }
+
+    pub(crate) fn is_complete(&self) -> bool {
+        match self {
+            Self::Length { remaining } => *remaining == 0,
+            Self::Chunked { state, .. } => *state == ChunkState::Done,
+            Self::UntilClose => false,
+        }
+    }
+
+    /// The server closed the connection; fails if the body was cut short
+    pub(crate) fn finish(&self) -> Result<()> {
+        match self {
+            Self::UntilClose => Ok(()),
+            _ if self.is_complete() => Ok(()),
+            _ => Err(VmixError::Protocol(
+                "connection closed before the end of the response body".to_string(),
+            )),
+        }
+    }
+
+    /// Whether the end of the body is known without the connection being closed
+    pub(crate) fn is_delimited(&self) -> bool {
+        !matches!(self, Self::UntilClose)
+    }
+}
+
+/// Comma-separated values of every `name` header
+fn header_values<'a>(
+    headers: &'a [(String, String)],
+    name: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    headers
+        .iter()
+        .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+        .flat_map(|(_, value)| value.split(','))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn decode_chunked(
+    state: &mut ChunkState,
+    line: &mut Vec<u8>,
+    input: &[u8],
+    body: &mut Vec<u8>,
+) -> Result<usize> {
+    let mut pos = 0;
+    while pos < input.len() && *state != ChunkState::Done {
+        match *state {
+            ChunkState::Data { remaining } => {
+                let n = (input.len() - pos).min(remaining);
+                body.extend_from_slice(&input[pos..pos + n]);
+                pos += n;
+                *state = if remaining == n {
+                    ChunkState::DataEnd
+                } else {
+                    ChunkState::Data {
+                        remaining: remaining - n,
+                    }
+                };
+            }
+            ChunkState::Size | ChunkState::DataEnd | ChunkState::Trailers => {
+                let Some(end) = input[pos..].iter().position(|&b| b == b'\n') else {
+                    line.extend_from_slice(&input[pos..]);
+                    check_line_length(line)?;
+                    return Ok(input.len());
+                };
+                line.extend_from_slice(&input[pos..pos + end]);
+                pos += end + 1;
+                check_line_length(line)?;
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                *state = next_chunk_state(*state, line)?;
+                line.clear();
+            }
+            ChunkState::Done => {}
+        }
+    }
+    Ok(pos)
+}
+
+/// State after a complete line of a chunked body, without its line ending
+fn next_chunk_state(state: ChunkState, line: &[u8]) -> Result<ChunkState> {
+    match state {
+        ChunkState::Size => {
+            let line = std::str::from_utf8(line)
+                .map_err(|_| VmixError::Protocol("invalid chunk size".to_string()))?;
+            // Chunk extensions after ';' are ignored
+            let size = line.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size, 16)
+                .map_err(|_| VmixError::Protocol(format!("invalid chunk size: {:?}", size)))?;
+            Ok(if size == 0 {
+                ChunkState::Trailers
+            } else {
+                ChunkState::Data { remaining: size }
+            })
+        }
+        ChunkState::DataEnd if line.is_empty() => Ok(ChunkState::Size),
+        ChunkState::DataEnd => Err(VmixError::Protocol(
+            "missing CRLF after chunk data".to_string(),
+        )),
+        // Trailer fields are ignored until the empty line ending the body
+        ChunkState::Trailers if line.is_empty() => Ok(ChunkState::Done),
+        other => Ok(other),
+    }
+}
+
+fn check_line_length(line: &[u8]) -> Result<()> {
+    if line.len() > MAX_LINE {
+        return Err(VmixError::Protocol("chunk line too long".to_string()));
+    }
+    Ok(())
+}
+
+/// Undo the `Content-Encoding` of a body
+///
+/// Only gzip is supported, and only with the `gzip` feature, which is also
+/// what makes the client advertise it in `Accept-Encoding`.
+#[cfg_attr(not(feature = "gzip"), allow(unused_variables))]
+pub(crate) fn decode_content(
+    headers: &[(String, String)],
+    body: Vec<u8>,
+    max_size: usize,
+) -> Result<Vec<u8>> {
+    let coding = headers
+        .iter()
+        .rev()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+        .map(|(_, value)| value.trim());
+    match coding {
+        None => Ok(body),
+        Some(coding) if coding.is_empty() || coding.eq_ignore_ascii_case("identity") => Ok(body),
+        #[cfg(feature = "gzip")]
+        Some(coding)
+            if coding.eq_ignore_ascii_case("gzip") || coding.eq_ignore_ascii_case("x-gzip") =>
+        {
+            gunzip(&body, max_size)
+        }
+        Some(coding) => Err(VmixError::Protocol(format!(
+            "unsupported Content-Encoding: {}",
+            coding
+        ))),
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn gunzip(body: &[u8], max_size: usize) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(body)
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|e| VmixError::Protocol(format!("invalid gzip body: {}", e)))?;
+    if decoded.len() > max_size {
+        return Err(VmixError::BodyTooLarge { limit: max_size });
+    }
+    Ok(decoded)
+}
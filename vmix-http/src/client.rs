@@ -1,205 +1,423 @@
-use crate::traits::VmixApiClient;
-use anyhow::Result;
+use crate::{
+    auth::Credentials,
+    pool::ConnectionPool,
+    request::{self, ClientConfig, function_query, sorted},
+    response::{ExchangeError, ResponseReader},
+    retry::{self, CircuitBreaker, CircuitBreakerConfig, CircuitState, RetryPolicy},
+    traits::VmixApiClient,
+    transport::{HttpTransport, timeout},
+};
 use async_trait::async_trait;
-use shiguredo_http11::{Request, ResponseDecoder};
-use std::{collections::HashMap, net::SocketAddr, time::Duration};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
-use tokio::time::timeout;
-use urlencoding::encode;
-use vmix_core::Vmix;
+use std::{collections::HashMap, fmt, io, sync::Arc, time::Duration};
+use vmix_core::{
+    Capabilities, FunctionParams, Vmix, VmixFunction,
+    error::{Result, VmixError},
+    functions::{InputRef, TitleField},
+};
+#[cfg(feature = "vmix-client")]
+use vmix_tcp::{ClientCapabilities, EventFilter, EventSubscription, Transport, VmixClient};
 use vmix_tcp::{InputNumber, TallyData};
+#[cfg(feature = "tokio")]
+use {crate::transport::TokioTransport, std::net::SocketAddr};
+
+pub use crate::response::HttpResponse;
+
+/// Client for the vMix HTTP API
+///
+/// Connections are kept open between requests (HTTP/1.1 keep-alive) and shared
+/// by the clones of a client, so polling vMix does not pay for a new TCP
+/// connection each time.
+///
+/// Runs on tokio by default; [`new_with_transport`](Self::new_with_transport)
+/// takes any other [`HttpTransport`]. Without the `tokio` feature there is no
+/// default transport, and the transport always has to be given.
+pub struct HttpVmixClient<T: HttpTransport = DefaultTransport> {
+    config: ClientConfig,
+    pool: Arc<ConnectionPool<T::Connection>>,
+    transport: T,
+}
+
+#[cfg(feature = "tokio")]
+type DefaultTransport = TokioTransport;
+#[cfg(not(feature = "tokio"))]
+type DefaultTransport = crate::transport::NoTransport;
+
+// Not derived, which would require the connections to be `Clone` and `Debug`
+impl<T: HttpTransport> Clone for HttpVmixClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            pool: self.pool.clone(),
+            transport: self.transport.clone(),
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
-pub struct HttpVmixClient {
-    host: String,
-    port: u16,
-    request_timeout: Duration,
+impl<T: HttpTransport> fmt::Debug for HttpVmixClient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpVmixClient")
+            .field("config", &self.config)
+            .field("pool", &self.pool)
+            .field("transport", &self.transport)
+            .finish()
+    }
 }
 
+#[cfg(feature = "tokio")]
 impl HttpVmixClient {
     pub fn new(addr: SocketAddr, request_timeout: Duration) -> Self {
-        Self {
-            host: addr.ip().to_string(),
-            port: addr.port(),
-            request_timeout,
-        }
+        Self::new_with_host_port(&addr.ip().to_string(), addr.port(), request_timeout)
     }
 
     pub fn new_with_host_port(host: &str, port: u16, request_timeout: Duration) -> Self {
+        Self::new_with_transport(host, port, request_timeout, TokioTransport)
+    }
+}
+
+impl<T: HttpTransport> HttpVmixClient<T> {
+    /// Client connecting, reading and waiting through `transport`
+    pub fn new_with_transport(
+        host: &str,
+        port: u16,
+        request_timeout: Duration,
+        transport: T,
+    ) -> Self {
         Self {
-            host: host.to_string(),
-            port,
-            request_timeout,
+            config: ClientConfig::new(host.to_string(), port, request_timeout),
+            pool: Arc::default(),
+            transport,
         }
     }
 
-    async fn send_request(
-        &self,
-        path: &str,
-        query_params: &HashMap<String, String>,
-    ) -> Result<Vec<u8>> {
-        // Build query string
-        let mut query_parts = Vec::new();
-        for (key, value) in query_params {
-            query_parts.push(format!("{}={}", encode(key), encode(value)));
-        }
-        let query_string = if query_parts.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", query_parts.join("&"))
-        };
+    /// Check functions against `capabilities` before sending them
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.config.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Authenticate to a password protected web controller with HTTP Basic authentication
+    ///
+    /// Requests with wrong credentials fail with [`VmixError::Unauthorized`].
+    pub fn with_basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.config.credentials = Some(Credentials::new(username, password));
+        self
+    }
 
-        let uri = format!("{}{}", path, query_string);
+    /// Authenticate with `credentials`, or not at all with `None`
+    pub fn with_credentials(mut self, credentials: Option<Credentials>) -> Self {
+        self.config.credentials = credentials;
+        self
+    }
 
-        // Create HTTP request
-        let request = Request::new("GET", &uri)
-            .header("Host", &format!("{}:{}", self.host, self.port))
-            .header("Connection", "close");
+    /// Retry requests that failed because vMix could not be reached
+    ///
+    /// By default every request is attempted once.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.config.retry = retry;
+        self
+    }
 
-        // Encode request
-        let request_bytes = request.encode();
+    /// Stop sending requests for a while after repeated failures
+    ///
+    /// The breaker is shared by the clones of the client made afterwards.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.config.breaker = Some(Arc::new(CircuitBreaker::new(config)));
+        self
+    }
 
-        // Connect to server
-        let addr = format!("{}:{}", self.host, self.port);
-        let mut stream = timeout(self.request_timeout, TcpStream::connect(&addr)).await??;
+    /// State of the circuit breaker, if there is one
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.config.breaker.as_ref().map(|breaker| breaker.state())
+    }
 
-        // Send request
-        stream.write_all(&request_bytes).await?;
-        stream.flush().await?;
+    /// Reuse connections between requests (enabled by default)
+    ///
+    /// Without keep-alive every request opens a new connection and asks vMix to
+    /// close it after the response.
+    pub fn with_keep_alive(mut self, keep_alive: bool) -> Self {
+        self.config.pool.keep_alive = keep_alive;
+        self
+    }
 
-        // Read response
-        let mut decoder = ResponseDecoder::new();
-        let mut temp_buffer = [0u8; 8192];
-        let mut response_opt = None;
+    /// Keep at most `max_idle` open connections for reuse (4 by default)
+    pub fn with_max_idle_connections(mut self, max_idle: usize) -> Self {
+        self.config.pool.max_idle = max_idle;
+        self
+    }
 
-        // Read until we get a complete response
-        loop {
-            let read_timeout = timeout(self.request_timeout, stream.read(&mut temp_buffer)).await?;
-            match read_timeout {
-                Ok(0) => break, // EOF
-                Ok(n) => {
-                    decoder.feed(&temp_buffer[..n])?;
+    /// Close connections unused for longer than `idle_timeout` instead of reusing them (30s by default)
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.config.pool.idle_timeout = idle_timeout;
+        self
+    }
 
-                    // Try to decode response
-                    if let Some(response) = decoder.decode()? {
-                        response_opt = Some(response);
-                        break;
-                    }
+    /// Fail requests whose response body is larger than `max_body_size` bytes (64 MiB by default)
+    ///
+    /// The limit applies to the decompressed body as well.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.config.max_body_size = max_body_size;
+        self
+    }
+
+    /// Number of open connections waiting to be reused
+    pub fn idle_connections(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Close the connections waiting to be reused
+    pub fn close_idle_connections(&self) {
+        self.pool.clear();
+    }
+
+    /// Capabilities used to check functions before they are sent, if known
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.config.capabilities
+    }
+
+    /// Read the vMix version from the XML state and remember its capabilities
+    pub async fn detect_capabilities(&mut self) -> Result<Capabilities> {
+        let state = self.get_xml_state().await?;
+        let capabilities = request::capabilities_of(&state)?;
+        self.config.capabilities = Some(capabilities);
+        Ok(capabilities)
+    }
+
+    /// Send a GET request for `path` with an already encoded query string,
+    /// retrying as the retry policy allows
+    ///
+    /// Requests that change vMix are not `idempotent`.
+    async fn send_query(
+        &self,
+        path: &str,
+        query: &str,
+        idempotent: bool,
+    ) -> Result<(u16, Vec<u8>)> {
+        let retry = &self.config.retry;
+        let breaker = &self.config.breaker;
+        let attempts = retry.attempts(idempotent);
+        let mut attempt = 1;
+        loop {
+            if let Some(breaker) = breaker {
+                breaker.check()?;
+            }
+            let result = self.send_once(path, query, idempotent).await;
+            if let Some(breaker) = breaker {
+                breaker.record(&result);
+            }
+            match result {
+                Err(err) if attempt < attempts && retry::is_transient(&err) => {
+                    self.transport.sleep(retry.delay(attempt)).await;
+                    attempt += 1;
                 }
-                Err(e) => return Err(anyhow::anyhow!("Failed to read response: {}", e)),
+                result => return result,
             }
         }
+    }
 
-        let response = response_opt.ok_or_else(|| anyhow::anyhow!("Failed to decode response"))?;
+    /// Make a single attempt at a GET request
+    ///
+    /// With keep-alive, an idle connection is reused when there is one. If the
+    /// server closed it, the request is sent once more on a new connection,
+    /// unless it changes vMix and may already have been received: vMix could
+    /// have executed it before closing the connection.
+    async fn send_once(&self, path: &str, query: &str, idempotent: bool) -> Result<(u16, Vec<u8>)> {
+        let request = self.config.encode_request(path, query);
+
+        if self.config.pool.keep_alive
+            && let Some(stream) = self
+                .pool
+                .checkout(self.config.pool.idle_timeout, |connection| {
+                    self.transport.is_open(connection)
+                })
+        {
+            match self.exchange(stream, &request).await {
+                // The server dropped the idle connection; reconnect below
+                Err(ExchangeError::Unsent(_)) => {}
+                Err(ExchangeError::Closed(_)) if idempotent => {}
+                result => return result.map_err(VmixError::from),
+            }
+        }
+
+        let stream = self.connect().await?;
+        self.exchange(stream, &request)
+            .await
+            .map_err(VmixError::from)
+    }
 
-        // Check status code
-        if !(200..300).contains(&response.status_code) {
-            return Err(anyhow::anyhow!(
-                "HTTP request failed with status: {}",
-                response.status_code
-            ));
+    async fn connect(&self) -> Result<T::Connection> {
+        let addr = self.config.addr();
+        let connect = self.transport.connect(&addr);
+        let timed_out = || VmixError::Timeout(format!("connecting to {}", addr));
+        match timeout(&self.transport, self.config.request_timeout, connect).await {
+            None => Err(timed_out()),
+            Some(Err(e)) if e.kind() == io::ErrorKind::TimedOut => Err(timed_out()),
+            Some(result) => result.map_err(|e| VmixError::Connect {
+                addr: addr.clone(),
+                source: e,
+            }),
         }
+    }
 
-        // Read body
-        // The decoder has already consumed the headers, so we need to read the body separately
-        // Check if there's a body based on Content-Length or Transfer-Encoding
-        let mut body = Vec::new();
-
-        // Try to get Content-Length from headers
-        let content_length = response
-            .headers
-            .iter()
-            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
-            .and_then(|(_, value)| value.parse::<usize>().ok());
-
-        if let Some(len) = content_length {
-            // Read exactly len bytes
-            let mut remaining = len;
-            while remaining > 0 {
-                let to_read = remaining.min(temp_buffer.len());
-                let read_timeout = timeout(
-                    self.request_timeout,
-                    stream.read(&mut temp_buffer[..to_read]),
-                )
-                .await?;
-                match read_timeout {
-                    Ok(0) => break, // EOF
-                    Ok(n) => {
-                        body.extend_from_slice(&temp_buffer[..n]);
-                        remaining -= n;
-                    }
-                    Err(e) => return Err(anyhow::anyhow!("Failed to read body: {}", e)),
+    /// Write a request and read the status and body of its response, keeping the
+    /// connection for reuse if the response allows it
+    async fn exchange(
+        &self,
+        mut stream: T::Connection,
+        request: &[u8],
+    ) -> std::result::Result<(u16, Vec<u8>), ExchangeError> {
+        // Send request
+        if let Err(e) = self.transport.write_all(&mut stream, request).await {
+            return Err(ExchangeError::Unsent(e.into()));
+        }
+
+        let mut reader = ResponseReader::new(self.config.max_body_size);
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read = self.transport.read(&mut stream, &mut buffer);
+            let read = timeout(&self.transport, self.config.request_timeout, read)
+                .await
+                .ok_or_else(|| VmixError::Timeout(reader.reading().to_string()))?;
+            match read {
+                Ok(0) => {
+                    reader.close()?;
+                    break;
                 }
-            }
-        } else {
-            // No Content-Length, read until EOF (Connection: close)
-            loop {
-                let read_timeout =
-                    timeout(self.request_timeout, stream.read(&mut temp_buffer)).await?;
-                match read_timeout {
-                    Ok(0) => break, // EOF
-                    Ok(n) => {
-                        body.extend_from_slice(&temp_buffer[..n]);
+                Ok(n) => {
+                    if reader.feed(&buffer[..n])? {
+                        break;
                     }
-                    Err(e) => return Err(anyhow::anyhow!("Failed to read body: {}", e)),
                 }
+                // Transports with blocking sockets time out themselves
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                    return Err(VmixError::Timeout(reader.reading().to_string()).into());
+                }
+                Err(e) => return Err(reader.read_error(e)),
             }
         }
 
-        Ok(body)
+        if self.config.pool.keep_alive && reader.is_reusable() {
+            self.pool.checkin(stream, &self.config.pool);
+        }
+        Ok(reader.into_response()?)
     }
 
+    /// Execute a vMix function
+    ///
+    /// The parameters are sent sorted by name; use
+    /// [`execute_function_with_params`](Self::execute_function_with_params)
+    /// to choose their order or repeat one.
     pub async fn execute_function(
         &self,
         function: &str,
         params: &HashMap<String, String>,
     ) -> Result<()> {
-        let mut query_params = HashMap::new();
-        query_params.insert("Function".to_string(), function.to_string());
-        query_params.extend(params.clone());
+        self.execute_function_with_response(function, params)
+            .await?;
+        Ok(())
+    }
+
+    /// Execute a vMix function and return the response to it
+    ///
+    /// Most functions answer with an empty body; those returning a value, such as
+    /// a title's text, return it in [`HttpResponse::body`].
+    pub async fn execute_function_with_response(
+        &self,
+        function: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<HttpResponse> {
+        self.execute_function_with_params(function, &sorted(params))
+            .await
+    }
+
+    /// Execute a vMix function with parameters sent in the given order
+    ///
+    /// Parameter names are checked first: a name vMix does not know fails with
+    /// [`VmixError::UnknownParam`] without being sent.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let params = FunctionParams::new().input(1).selected_name("Title.Text").value("Hello");
+    /// client.execute_function_with_params("SetText", &params).await?;
+    /// ```
+    pub async fn execute_function_with_params(
+        &self,
+        function: &str,
+        params: &FunctionParams,
+    ) -> Result<HttpResponse> {
+        let query = function_query(function, params)?;
+        let (status, body) = self.send_query("/api", &query, false).await?;
+        HttpResponse::new(status, body)
+    }
+
+    /// URL executing a vMix function, without credentials
+    ///
+    /// The same function and parameters always give the same URL, so it can be
+    /// logged, and replayed by parsing its query with [`FunctionParams::parse`].
+    pub fn function_url(&self, function: &str, params: &FunctionParams) -> Result<String> {
+        Ok(format!(
+            "{}?{}",
+            self.config.base_url(),
+            function_query(function, params)?
+        ))
+    }
 
-        self.send_request("/api", &query_params).await?;
+    /// Execute a typed vMix function
+    ///
+    /// The query string is the same one sent by the TCP client for this function.
+    /// Once the capabilities are known, functions the connected vMix does not support
+    /// fail with [`VmixError::Unsupported`] without being sent.
+    pub async fn execute(&self, function: &VmixFunction) -> Result<()> {
+        self.execute_with_response(function).await?;
         Ok(())
     }
 
+    /// Execute a typed vMix function and return the response to it
+    pub async fn execute_with_response(&self, function: &VmixFunction) -> Result<HttpResponse> {
+        if let Some(capabilities) = &self.config.capabilities {
+            capabilities.check(function)?;
+        }
+        self.execute_function_with_params(&function.name(), &function.params())
+            .await
+    }
+
+    /// Query a single value from the vMix XML state using an XPath expression
+    ///
+    /// The HTTP counterpart of the TCP `XMLTEXT` command, e.g.
+    /// `vmix/inputs/input[@number='1']/@title`. A path that matches nothing
+    /// returns an empty string.
+    pub async fn xml_text(&self, path: &str) -> Result<String> {
+        let query = FunctionParams::new().with("XPath", path);
+        let (_, body) = self
+            .send_query("/api", &query.to_query_string(), true)
+            .await?;
+        Ok(String::from_utf8(body)?.trim().to_string())
+    }
+
     pub async fn get_xml_state(&self) -> Result<Vmix> {
-        let body = self.send_request("/api", &HashMap::new()).await?;
+        let (_, body) = self.send_query("/api", "", true).await?;
         let xml_text = String::from_utf8(body)?;
         let vmix_data: Vmix = vmix_core::from_str(&xml_text)?;
         Ok(vmix_data)
     }
 
     pub async fn get_tally_data(&self) -> Result<HashMap<InputNumber, TallyData>> {
-        // HTTP API doesn't have direct TALLY command, so we need to derive it from XML state
-        // This simulates the TCP TALLY response by analyzing the XML state
         let vmix_state = self.get_xml_state().await?;
-        let mut tally_map = HashMap::new();
-
-        // Parse active and preview inputs to determine tally states
-        let active_input: InputNumber = vmix_state.active.parse().unwrap_or(0);
-        let preview_input: InputNumber = vmix_state.preview.parse().unwrap_or(0);
-
-        // Populate tally data for all inputs (up to 1000 as per vMix spec)
-        for input in &vmix_state.inputs.input {
-            let input_number: InputNumber = input.number.parse().unwrap_or(0);
-
-            let tally_state = if input_number == active_input {
-                TallyData::PROGRAM
-            } else if input_number == preview_input {
-                TallyData::PREVIEW
-            } else {
-                TallyData::OFF
-            };
-
-            tally_map.insert(input_number, tally_state);
-        }
+        Ok(request::tally_of(&vmix_state))
+    }
 
-        Ok(tally_map)
+    /// Tally of the input with `key`, from the endpoint used by vMix web tally lights
+    ///
+    /// Asks `/tallyupdate/?key=` about a single input instead of downloading
+    /// and parsing the whole XML state.
+    pub async fn tally_for_key(&self, key: &str) -> Result<TallyData> {
+        let (_, body) = self
+            .send_query("/tallyupdate/", &request::tally_query(key), true)
+            .await?;
+        request::tally_of_colour(&String::from_utf8(body)?)
     }
 
     pub async fn is_connected(&self) -> bool {
-        self.send_request("/api", &HashMap::new()).await.is_ok()
+        self.send_query("/api", "", true).await.is_ok()
     }
 
     pub async fn get_active_input(&self) -> Result<InputNumber> {
@@ -212,35 +430,36 @@ impl HttpVmixClient {
         Ok(vmix_data.preview.parse().unwrap_or(0))
     }
 
+    /// URL of the API, without credentials
     pub fn get_base_url(&self) -> String {
-        format!("http://{}:{}/api", self.host, self.port)
+        self.config.base_url()
     }
 }
 
 // Helper function for common vMix functions
-impl HttpVmixClient {
+impl<T: HttpTransport> HttpVmixClient<T> {
     pub async fn cut(&self) -> Result<()> {
-        self.execute_function("Cut", &HashMap::new()).await
+        self.execute(&VmixFunction::cut()).await
     }
 
     pub async fn fade(&self, duration_ms: Option<u32>) -> Result<()> {
-        let mut params = HashMap::new();
-        if let Some(duration) = duration_ms {
-            params.insert("Duration".to_string(), duration.to_string());
-        }
-        self.execute_function("Fade", &params).await
+        self.execute(&VmixFunction::fade(duration_ms)).await
     }
 
     pub async fn preview_input(&self, input: InputNumber) -> Result<()> {
-        let mut params = HashMap::new();
-        params.insert("Input".to_string(), input.to_string());
-        self.execute_function("PreviewInput", &params).await
+        self.execute(&VmixFunction::PreviewInput {
+            input: InputRef::Number(input),
+            mix: None,
+        })
+        .await
     }
 
     pub async fn active_input(&self, input: InputNumber) -> Result<()> {
-        let mut params = HashMap::new();
-        params.insert("Input".to_string(), input.to_string());
-        self.execute_function("ActiveInput", &params).await
+        self.execute(&VmixFunction::ActiveInput {
+            input: InputRef::Number(input),
+            mix: None,
+        })
+        .await
     }
 
     pub async fn set_text(
@@ -249,40 +468,35 @@ impl HttpVmixClient {
         selected_name: &str,
         value: &str,
     ) -> Result<()> {
-        let mut params = HashMap::new();
-        params.insert("Input".to_string(), input.to_string());
-        params.insert("SelectedName".to_string(), selected_name.to_string());
-        params.insert("Value".to_string(), value.to_string());
-        self.execute_function("SetText", &params).await
+        self.execute(&VmixFunction::SetText {
+            input: InputRef::Number(input),
+            field: TitleField::Name(selected_name.to_string()),
+            value: value.to_string(),
+        })
+        .await
     }
 
     pub async fn start_recording(&self) -> Result<()> {
-        self.execute_function("StartRecording", &HashMap::new())
-            .await
+        self.execute(&VmixFunction::StartRecording).await
     }
 
     pub async fn stop_recording(&self) -> Result<()> {
-        self.execute_function("StopRecording", &HashMap::new())
-            .await
+        self.execute(&VmixFunction::StopRecording).await
     }
 
     pub async fn start_streaming(&self) -> Result<()> {
-        self.execute_function("StartStreaming", &HashMap::new())
+        self.execute(&VmixFunction::StartStreaming { channel: None })
             .await
     }
 
     pub async fn stop_streaming(&self) -> Result<()> {
-        self.execute_function("StopStreaming", &HashMap::new())
+        self.execute(&VmixFunction::StopStreaming { channel: None })
             .await
     }
 }
 
-// HttpVmixClientはマルチスレッド環境で安全に使用できる
-unsafe impl Send for HttpVmixClient {}
-unsafe impl Sync for HttpVmixClient {}
-
 #[async_trait]
-impl VmixApiClient for HttpVmixClient {
+impl<T: HttpTransport> VmixApiClient for HttpVmixClient<T> {
     async fn execute_function(
         &self,
         function: &str,
@@ -291,6 +505,26 @@ impl VmixApiClient for HttpVmixClient {
         self.execute_function(function, params).await
     }
 
+    async fn execute_function_with_response(
+        &self,
+        function: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<HttpResponse> {
+        self.execute_function_with_response(function, params).await
+    }
+
+    async fn execute_function_with_params(
+        &self,
+        function: &str,
+        params: &FunctionParams,
+    ) -> Result<HttpResponse> {
+        self.execute_function_with_params(function, params).await
+    }
+
+    async fn xml_text(&self, path: &str) -> Result<String> {
+        self.xml_text(path).await
+    }
+
     async fn get_xml_state(&self) -> Result<Vmix> {
         self.get_xml_state().await
     }
@@ -299,6 +533,10 @@ impl VmixApiClient for HttpVmixClient {
         self.get_tally_data().await
     }
 
+    async fn tally_for_key(&self, key: &str) -> Result<TallyData> {
+        self.tally_for_key(key).await
+    }
+
     async fn is_connected(&self) -> bool {
         self.is_connected().await
     }
@@ -311,3 +549,37 @@ impl VmixApiClient for HttpVmixClient {
         self.get_preview_input().await
     }
 }
+
+#[cfg(feature = "vmix-client")]
+#[async_trait]
+impl<T: HttpTransport> VmixClient for HttpVmixClient<T> {
+    fn transport_capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            transport: Transport::Http,
+            events: false,
+            function_responses: false,
+        }
+    }
+
+    async fn execute(&self, function: &VmixFunction) -> Result<()> {
+        HttpVmixClient::execute(self, function).await
+    }
+
+    async fn fetch_state(&self) -> Result<Vmix> {
+        self.get_xml_state().await
+    }
+
+    async fn tally(&self) -> Result<HashMap<InputNumber, TallyData>> {
+        self.get_tally_data().await
+    }
+
+    async fn subscribe(&self, _filter: EventFilter, _capacity: usize) -> Result<EventSubscription> {
+        Err(VmixError::TransportUnsupported {
+            operation: "event subscriptions",
+        })
+    }
+
+    async fn is_connected(&self) -> bool {
+        HttpVmixClient::is_connected(self).await
+    }
+}
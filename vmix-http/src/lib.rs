@@ -1,13 +1,60 @@
+#[cfg(feature = "async")]
+mod auth;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "async")]
+mod body;
+#[cfg(feature = "async")]
 pub mod client;
+#[cfg(feature = "async")]
+mod pool;
+#[cfg(feature = "async")]
+mod request;
+#[cfg(feature = "async")]
+mod response;
+#[cfg(feature = "async")]
+pub mod retry;
+#[cfg(feature = "async")]
 pub mod traits;
+#[cfg(feature = "async")]
+pub mod transport;
+#[cfg(feature = "tokio")]
+pub mod watcher;
 
 // Re-export commonly used types
+#[cfg(feature = "async")]
+pub use auth::Credentials;
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingHttpVmixClient;
+#[cfg(feature = "async")]
 pub use client::HttpVmixClient;
+#[cfg(feature = "async")]
+pub use response::HttpResponse;
+#[cfg(feature = "async")]
+pub use retry::{CircuitBreakerConfig, CircuitState, RetryPolicy};
+#[cfg(feature = "async")]
 pub use traits::VmixApiClient;
+#[cfg(feature = "futures-io")]
+pub use transport::FuturesIoTransport;
+#[cfg(feature = "async")]
+pub use transport::HttpTransport;
+#[cfg(feature = "tokio")]
+pub use transport::TokioTransport;
+#[cfg(feature = "tokio")]
+pub use watcher::{HttpStateWatcher, WatchConfig, WatchEvent};
+
+// Re-export the traits `FuturesIoTransport` connections implement
+#[cfg(feature = "futures-io")]
+pub use futures_io;
 
 // Re-export vmix-core for convenience
 pub use vmix_core;
+pub use vmix_core::{Capabilities, FunctionParams, VmixError, VmixFunction, VmixVersion};
 
 // Re-export types from vmix-tcp for convenience (InputNumber, TallyData)
 // These are needed by the HTTP client trait
 pub use vmix_tcp::{InputNumber, TallyData};
+
+// The transport-agnostic client trait implemented by `HttpVmixClient`
+#[cfg(feature = "vmix-client")]
+pub use vmix_tcp::{ClientCapabilities, Transport, VmixClient};
@@ -0,0 +1,100 @@
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How a client reuses connections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PoolConfig {
+    pub(crate) keep_alive: bool,
+    /// Open connections kept for reuse at most
+    pub(crate) max_idle: usize,
+    /// Connections unused for longer are closed instead of being reused
+    pub(crate) idle_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive: true,
+            max_idle: 4,
+            idle_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+struct IdleConnection<S> {
+    stream: S,
+    idle_since: Instant,
+}
+
+/// Idle keep-alive connections shared by the clones of a client
+pub(crate) struct ConnectionPool<S> {
+    idle: Mutex<Vec<IdleConnection<S>>>,
+}
+
+impl<S> Default for ConnectionPool<S> {
+    fn default() -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<S> fmt::Debug for ConnectionPool<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionPool")
+            .field("idle", &self.len())
+            .finish()
+    }
+}
+
+impl<S> ConnectionPool<S> {
+    /// Take the most recently used connection that is still open
+    ///
+    /// Connections idle for longer than `idle_timeout`, and connections the
+    /// server has closed in the meantime, are dropped.
+    pub(crate) fn checkout(
+        &self,
+        idle_timeout: Duration,
+        mut is_open: impl FnMut(&mut S) -> bool,
+    ) -> Option<S> {
+        let mut idle = self.idle.lock().ok()?;
+        idle.retain(|c| c.idle_since.elapsed() < idle_timeout);
+        while let Some(mut connection) = idle.pop() {
+            if is_open(&mut connection.stream) {
+                return Some(connection.stream);
+            }
+        }
+        None
+    }
+
+    /// Keep a connection whose response was read completely for reuse
+    pub(crate) fn checkin(&self, stream: S, config: &PoolConfig) {
+        let Ok(mut idle) = self.idle.lock() else {
+            return;
+        };
+        if config.max_idle == 0 {
+            return;
+        }
+        // Drop the connections that have been idle the longest
+        while idle.len() >= config.max_idle {
+            idle.remove(0);
+        }
+        idle.push(IdleConnection {
+            stream,
+            idle_since: Instant::now(),
+        });
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.idle.lock().map(|idle| idle.len()).unwrap_or(0)
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.clear();
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    auth::Credentials,
+    pool::PoolConfig,
+    retry::{CircuitBreaker, RetryPolicy},
+};
+use shiguredo_http11::Request;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use vmix_core::{
+    Capabilities, FunctionParams, Vmix,
+    error::{Result, VmixError},
+};
+use vmix_tcp::{InputNumber, TallyData};
+
+/// Largest response body accepted by default
+const DEFAULT_MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+/// Settings shared by the async and the blocking client
+#[derive(Debug, Clone)]
+pub(crate) struct ClientConfig {
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) request_timeout: Duration,
+    pub(crate) capabilities: Option<Capabilities>,
+    pub(crate) pool: PoolConfig,
+    pub(crate) max_body_size: usize,
+    pub(crate) credentials: Option<Credentials>,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl ClientConfig {
+    pub(crate) fn new(host: String, port: u16, request_timeout: Duration) -> Self {
+        Self {
+            host,
+            port,
+            request_timeout,
+            capabilities: None,
+            pool: PoolConfig::default(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            credentials: None,
+            retry: RetryPolicy::none(),
+            breaker: None,
+        }
+    }
+
+    pub(crate) fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// URL of the API, without credentials
+    pub(crate) fn base_url(&self) -> String {
+        format!("http://{}/api", self.addr())
+    }
+
+    /// Encoded GET request for `path` with an already encoded query string
+    pub(crate) fn encode_request(&self, path: &str, query: &str) -> Vec<u8> {
+        let uri = if query.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}?{}", path, query)
+        };
+        let connection = if self.pool.keep_alive {
+            "keep-alive"
+        } else {
+            "close"
+        };
+
+        let request = Request::new("GET", &uri)
+            .header("Host", &self.addr())
+            .header("Connection", connection);
+        let request = match &self.credentials {
+            Some(credentials) => request.header("Authorization", &credentials.authorization()),
+            None => request,
+        };
+        #[cfg(feature = "gzip")]
+        let request = request.header("Accept-Encoding", "gzip");
+        request.encode()
+    }
+}
+
+/// Parameters of a map, sorted by name so the query string does not change between calls
+pub(crate) fn sorted(params: &HashMap<String, String>) -> FunctionParams {
+    let mut params: Vec<_> = params.iter().collect();
+    params.sort();
+    params
+        .into_iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Encoded `/api` query executing `function`, after checking the parameter names
+pub(crate) fn function_query(function: &str, params: &FunctionParams) -> Result<String> {
+    params.validate()?;
+    let query = FunctionParams::new().with("Function", function);
+    Ok(if params.is_empty() {
+        query.to_query_string()
+    } else {
+        format!("{}&{}", query, params)
+    })
+}
+
+/// Capabilities of the vMix version in the XML state
+pub(crate) fn capabilities_of(state: &Vmix) -> Result<Capabilities> {
+    Capabilities::from_snapshot(state)
+        .ok_or_else(|| VmixError::Protocol(format!("invalid version: {:?}", state.version)))
+}
+
+/// Tally of every input, derived from the XML state
+///
+/// The HTTP API has no TALLY command; this simulates the TCP TALLY response
+/// from the active and preview inputs.
+pub(crate) fn tally_of(state: &Vmix) -> HashMap<InputNumber, TallyData> {
+    let active_input: InputNumber = state.active.parse().unwrap_or(0);
+    let preview_input: InputNumber = state.preview.parse().unwrap_or(0);
+
+    state
+        .inputs
+        .input
+        .iter()
+        .map(|input| {
+            let input_number: InputNumber = input.number.parse().unwrap_or(0);
+            let tally_state = if input_number == active_input {
+                TallyData::PROGRAM
+            } else if input_number == preview_input {
+                TallyData::PREVIEW
+            } else {
+                TallyData::OFF
+            };
+            (input_number, tally_state)
+        })
+        .collect()
+}
+
+/// Query of the `/tallyupdate/` request for the input with `key`
+pub(crate) fn tally_query(key: &str) -> String {
+    FunctionParams::new().with("key", key).to_query_string()
+}
+
+/// Tally of the colour returned by `/tallyupdate/`
+///
+/// vMix answers with a script such as `tallyChange("#ff0000");` for its web
+/// tally page: red for program, green for preview, anything else when the
+/// input is neither.
+pub(crate) fn tally_of_colour(body: &str) -> Result<TallyData> {
+    let body = body.trim();
+    let colour = match body.split('"').nth(1) {
+        Some(quoted) => quoted,
+        None => body.trim_end_matches(';'),
+    }
+    .trim();
+
+    let (red, green) = match colour.to_ascii_lowercase().as_str() {
+        "red" => (0xff, 0),
+        "green" | "lime" => (0, 0xff),
+        "" | "black" | "gray" | "grey" | "transparent" => (0, 0),
+        other => rgb(other)
+            .ok_or_else(|| VmixError::Protocol(format!("unexpected tally colour: {:?}", colour)))?,
+    };
+    Ok(if red >= 0x80 && red > green * 2 {
+        TallyData::PROGRAM
+    } else if green >= 0x80 && green > red * 2 {
+        TallyData::PREVIEW
+    } else {
+        TallyData::OFF
+    })
+}
+
+/// Red and green components of a `#rgb` or `#rrggbb` colour
+fn rgb(colour: &str) -> Option<(u16, u16)> {
+    let hex = colour.strip_prefix('#').filter(|hex| hex.is_ascii())?;
+    let component = |digits: &str| u8::from_str_radix(digits, 16).ok().map(u16::from);
+    match hex.len() {
+        3 => Some((component(&hex[0..1])? * 0x11, component(&hex[1..2])? * 0x11)),
+        6 => Some((component(&hex[0..2])?, component(&hex[2..4])?)),
+        _ => None,
+    }
+}
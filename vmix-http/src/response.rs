@@ -0,0 +1,220 @@
+use crate::body::{self, BodyDecoder};
+use shiguredo_http11::ResponseDecoder;
+use std::io;
+use vmix_core::error::{Result, VmixError};
+
+/// Largest response head (status line and headers) accepted
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// Response of the HTTP API to a function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub(crate) fn new(status: u16, body: Vec<u8>) -> Result<Self> {
+        Ok(Self {
+            status,
+            body: String::from_utf8(body)?,
+        })
+    }
+}
+
+/// Why a request on a connection failed
+pub(crate) enum ExchangeError {
+    /// The request could not be written, so vMix did not receive it
+    Unsent(VmixError),
+    /// The connection was closed before any of the response arrived; vMix may
+    /// have received the request
+    Closed(VmixError),
+    Failed(VmixError),
+}
+
+impl From<VmixError> for ExchangeError {
+    fn from(err: VmixError) -> Self {
+        Self::Failed(err)
+    }
+}
+
+impl From<ExchangeError> for VmixError {
+    fn from(err: ExchangeError) -> Self {
+        match err {
+            ExchangeError::Unsent(err)
+            | ExchangeError::Closed(err)
+            | ExchangeError::Failed(err) => err,
+        }
+    }
+}
+
+/// Status line, headers and body framing of a response
+struct Head {
+    status: u16,
+    headers: Vec<(String, String)>,
+    framing: BodyDecoder,
+}
+
+/// Reads a response from the bytes received on a connection
+///
+/// Does no I/O itself, so the async and the blocking client read responses
+/// the same way.
+pub(crate) struct ResponseReader {
+    max_body_size: usize,
+    /// Bytes received before the end of the head
+    buffer: Vec<u8>,
+    head: Option<Head>,
+    body: Vec<u8>,
+    /// Bytes were received after the end of the body
+    leftover: bool,
+}
+
+impl ResponseReader {
+    pub(crate) fn new(max_body_size: usize) -> Self {
+        Self {
+            max_body_size,
+            buffer: Vec::new(),
+            head: None,
+            body: Vec::new(),
+            leftover: false,
+        }
+    }
+
+    /// What is being read, for timeout errors
+    pub(crate) fn reading(&self) -> &'static str {
+        if self.head.is_none() {
+            "reading response headers"
+        } else {
+            "reading response body"
+        }
+    }
+
+    /// Use bytes received on the connection; returns whether the response is complete
+    ///
+    /// Fails as soon as the head shows the credentials were rejected.
+    pub(crate) fn feed(&mut self, data: &[u8]) -> Result<bool> {
+        if self.head.is_some() {
+            return self.feed_body(data);
+        }
+
+        self.buffer.extend_from_slice(data);
+        let Some(pos) = self.buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+            if self.buffer.len() > MAX_HEAD_SIZE {
+                return Err(VmixError::Protocol(
+                    "response headers too large".to_string(),
+                ));
+            }
+            return Ok(false);
+        };
+
+        // Bytes received past the head are the start of the body
+        let rest = self.buffer.split_off(pos + 4);
+        self.head = Some(parse_head(&self.buffer)?);
+        self.buffer = Vec::new();
+        self.feed_body(&rest)
+    }
+
+    fn feed_body(&mut self, data: &[u8]) -> Result<bool> {
+        let Some(head) = &mut self.head else {
+            return Ok(false);
+        };
+        let used = head.framing.decode(data, &mut self.body)?;
+        if self.body.len() > self.max_body_size {
+            return Err(VmixError::BodyTooLarge {
+                limit: self.max_body_size,
+            });
+        }
+        if head.framing.is_complete() {
+            self.leftover = used < data.len();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// The server closed the connection; fails unless that ends the response
+    pub(crate) fn close(&self) -> std::result::Result<(), ExchangeError> {
+        match &self.head {
+            Some(head) => Ok(head.framing.finish()?),
+            None => {
+                let err = VmixError::Protocol("failed to decode response".to_string());
+                Err(self.failed(err))
+            }
+        }
+    }
+
+    /// Reading from the connection failed
+    pub(crate) fn read_error(&self, err: io::Error) -> ExchangeError {
+        self.failed(err.into())
+    }
+
+    fn failed(&self, err: VmixError) -> ExchangeError {
+        if self.head.is_none() && self.buffer.is_empty() {
+            ExchangeError::Closed(err)
+        } else {
+            ExchangeError::Failed(err)
+        }
+    }
+
+    /// Whether the connection can carry another request after this complete response
+    ///
+    /// Only a connection whose successful response ended exactly where
+    /// announced can.
+    pub(crate) fn is_reusable(&self) -> bool {
+        let Some(head) = &self.head else {
+            return false;
+        };
+        let close = head
+            .headers
+            .iter()
+            .any(|(n, v)| n.eq_ignore_ascii_case("connection") && v.eq_ignore_ascii_case("close"));
+        is_success(head.status)
+            && head.framing.is_complete()
+            && head.framing.is_delimited()
+            && !self.leftover
+            && !close
+    }
+
+    /// Status and decoded body of the complete response, failing for
+    /// unsuccessful statuses with the text vMix sent
+    pub(crate) fn into_response(self) -> Result<(u16, Vec<u8>)> {
+        let head = self
+            .head
+            .ok_or_else(|| VmixError::Protocol("failed to decode response".to_string()))?;
+        let body = body::decode_content(&head.headers, self.body, self.max_body_size)?;
+        if !is_success(head.status) {
+            return Err(VmixError::HttpStatus {
+                status: head.status,
+                body: String::from_utf8_lossy(&body).trim().to_string(),
+            });
+        }
+        Ok((head.status, body))
+    }
+}
+
+fn is_success(status: u16) -> bool {
+    (200..300).contains(&status)
+}
+
+/// Decode a response head, failing if the credentials were rejected
+fn parse_head(head: &[u8]) -> Result<Head> {
+    let mut decoder = ResponseDecoder::new();
+    decoder
+        .feed(head)
+        .map_err(|e| VmixError::Protocol(e.to_string()))?;
+    let response = decoder
+        .decode()
+        .map_err(|e| VmixError::Protocol(e.to_string()))?
+        .ok_or_else(|| VmixError::Protocol("failed to decode response".to_string()))?;
+
+    // Check status code
+    if response.status_code == 401 {
+        return Err(VmixError::Unauthorized);
+    }
+
+    let framing = BodyDecoder::new(response.status_code, &response.headers)?;
+    Ok(Head {
+        status: response.status_code,
+        headers: response.headers,
+        framing,
+    })
+}
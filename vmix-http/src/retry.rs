@@ -0,0 +1,235 @@
+use std::{
+    hash::{BuildHasher, RandomState},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use vmix_core::error::{Result, VmixError};
+
+/// How often an [`HttpVmixClient`](crate::HttpVmixClient) attempts a request
+///
+/// Reads such as `get_xml_state` or `xml_text` are retried after transport
+/// failures (timeouts, refused or reset connections, 502, 503 and 504 statuses). Functions
+/// change vMix, and a function whose response was lost may still have been
+/// executed, so they are only retried after [`retry_functions`](Self::retry_functions).
+/// The delay doubles after every attempt, with a random part so clients
+/// retrying together spread out.
+///
+/// # Example
+/// ```rust,ignore
+/// let policy = RetryPolicy::new(4)
+///     .backoff(Duration::from_millis(100), Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_functions: bool,
+}
+
+impl RetryPolicy {
+    /// Make up to `max_attempts` attempts, the first one included
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            retry_functions: false,
+        }
+    }
+
+    /// A single attempt, the default
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Wait about `initial` before the first retry, doubling up to `max`
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Also retry functions, accepting that one may be executed twice
+    pub fn retry_functions(mut self, retry_functions: bool) -> Self {
+        self.retry_functions = retry_functions;
+        self
+    }
+
+    pub(crate) fn attempts(&self, idempotent: bool) -> u32 {
+        if idempotent || self.retry_functions {
+            self.max_attempts
+        } else {
+            1
+        }
+    }
+
+    /// Delay after failed attempt number `attempt`, starting at 1
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        // Between half and all of the delay
+        delay / 2 + random_up_to(delay / 2)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Whether an error means vMix could not be reached or could not answer
+///
+/// Only these errors are retried and counted by the circuit breaker; a
+/// rejected request would fail the same way again. vMix answers functions that
+/// fail with 500, so that status means vMix is up and is not counted.
+pub(crate) fn is_transient(err: &VmixError) -> bool {
+    match err {
+        VmixError::Connect { .. }
+        | VmixError::Timeout(_)
+        | VmixError::Disconnected
+        | VmixError::Io(_) => true,
+        VmixError::HttpStatus { status, .. } => matches!(status, 502..=504),
+        _ => false,
+    }
+}
+
+/// When an [`HttpVmixClient`](crate::HttpVmixClient) stops sending requests
+///
+/// After `failure_threshold` transient failures in a row the circuit opens:
+/// requests fail with [`VmixError::CircuitOpen`] without being sent, for
+/// `open_for`. Then a single request is let through; its success closes the
+/// circuit, its failure opens it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    failure_threshold: u32,
+    open_for: Duration,
+}
+
+impl CircuitBreakerConfig {
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+        }
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(10))
+    }
+}
+
+/// Whether a circuit breaker lets requests through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent
+    Closed,
+    /// Requests fail without being sent
+    Open,
+    /// A single request is sent to find out whether vMix is back
+    HalfOpen,
+}
+
+#[derive(Debug)]
+enum Circuit {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A trial request was let through; another one is let through after `until`
+    /// in case the first one was cancelled
+    HalfOpen {
+        until: Instant,
+    },
+}
+
+/// Circuit breaker shared by the clones of a client
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuit: Mutex<Circuit>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuit: Mutex::new(Circuit::Closed { failures: 0 }),
+        }
+    }
+
+    /// Fail if a request must not be sent now
+    pub(crate) fn check(&self) -> Result<()> {
+        let mut circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match *circuit {
+            Circuit::Closed { .. } => Ok(()),
+            Circuit::Open { until } | Circuit::HalfOpen { until } => {
+                let now = Instant::now();
+                if now >= until {
+                    *circuit = Circuit::HalfOpen {
+                        until: now + self.config.open_for,
+                    };
+                    Ok(())
+                } else {
+                    Err(VmixError::CircuitOpen {
+                        retry_after: until - now,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Record the outcome of a request that was sent
+    pub(crate) fn record(&self, result: &Result<impl Sized>) {
+        let mut circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let failed = result.as_ref().err().is_some_and(is_transient);
+        *circuit = match (&*circuit, failed) {
+            (_, false) => Circuit::Closed { failures: 0 },
+            (Circuit::Closed { failures }, true)
+                if failures + 1 < self.config.failure_threshold =>
+            {
+                Circuit::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (_, true) => Circuit::Open {
+                until: Instant::now() + self.config.open_for,
+            },
+        };
+    }
+
+    pub(crate) fn state(&self) -> CircuitState {
+        let circuit = match self.circuit.lock() {
+            Ok(circuit) => circuit,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match *circuit {
+            Circuit::Closed { .. } => CircuitState::Closed,
+            Circuit::Open { until } if Instant::now() < until => CircuitState::Open,
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// A random duration between zero and `max`
+pub(crate) fn random_up_to(max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    let random = RandomState::new().hash_one(Instant::now());
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
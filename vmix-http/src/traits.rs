@@ -1,7 +1,7 @@
-use anyhow::Result;
+use crate::HttpResponse;
 use async_trait::async_trait;
 use std::collections::HashMap;
-use vmix_core::Vmix;
+use vmix_core::{FunctionParams, Vmix, error::Result};
 use vmix_tcp::{InputNumber, TallyData};
 
 /// HTTP vMix API client trait
@@ -31,6 +31,34 @@ pub trait VmixApiClient {
         params: &HashMap<String, String>,
     ) -> Result<()>;
 
+    /// Execute a vMix function and return the status and body of the response
+    async fn execute_function_with_response(
+        &self,
+        function: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<HttpResponse>;
+
+    /// Execute a vMix function with parameters sent in the given order
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let params = FunctionParams::new().input(1).duration(1000);
+    /// client.execute_function_with_params("Fade", &params).await?;
+    /// ```
+    async fn execute_function_with_params(
+        &self,
+        function: &str,
+        params: &FunctionParams,
+    ) -> Result<HttpResponse>;
+
+    /// Query a single value from the vMix XML state using an XPath expression
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let title = client.xml_text("vmix/inputs/input[@number='1']/@title").await?;
+    /// ```
+    async fn xml_text(&self, path: &str) -> Result<String>;
+
     /// Get the complete vMix XML state
     ///
     /// Returns a structured representation of the current vMix configuration
@@ -43,6 +71,15 @@ pub trait VmixApiClient {
     /// This is useful for lighting systems and input status displays.
     async fn get_tally_data(&self) -> Result<HashMap<InputNumber, TallyData>>;
 
+    /// Get the tally of a single input by its key
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let input = &client.get_xml_state().await?.inputs.input[0];
+    /// let tally = client.tally_for_key(&input.key).await?;
+    /// ```
+    async fn tally_for_key(&self, key: &str) -> Result<TallyData>;
+
     /// Check if the client is connected and the vMix instance is responsive
     async fn is_connected(&self) -> bool;
 
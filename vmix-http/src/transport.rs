@@ -0,0 +1,251 @@
+use std::{
+    fmt,
+    future::{Future, poll_fn},
+    io,
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
+
+/// How an [`HttpVmixClient`](crate::HttpVmixClient) connects, reads, writes and waits
+///
+/// The client does everything else itself: encoding requests, decoding
+/// responses, reusing connections, retrying and timing out. Implement this
+/// trait to run it on an executor other than tokio; [`TokioTransport`] is the
+/// default, and `FuturesIoTransport` (`futures-io` feature) works with any
+/// stream implementing the `futures-io` traits.
+pub trait HttpTransport: Clone + fmt::Debug + Send + Sync + 'static {
+    /// An open connection
+    type Connection: Send + 'static;
+
+    /// Open a connection to `addr`, given as `host:port`
+    fn connect(&self, addr: &str) -> impl Future<Output = io::Result<Self::Connection>> + Send;
+
+    /// Read into `buf`, returning the number of bytes read, or 0 once the server closed the connection
+    fn read(
+        &self,
+        connection: &mut Self::Connection,
+        buf: &mut [u8],
+    ) -> impl Future<Output = io::Result<usize>> + Send;
+
+    /// Write and flush all of `buf`
+    fn write_all(
+        &self,
+        connection: &mut Self::Connection,
+        buf: &[u8],
+    ) -> impl Future<Output = io::Result<()>> + Send;
+
+    /// Wait for `duration`, for timeouts and between retries
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+
+    /// Whether an idle connection can still be written to, without waiting
+    ///
+    /// An idle connection has nothing to read: end of file means the server
+    /// closed it, and unexpected data means the previous response was not what
+    /// we thought.
+    fn is_open(&self, connection: &mut Self::Connection) -> bool;
+}
+
+/// Run `future` for at most `duration`, returning `None` if it took longer
+pub(crate) async fn timeout<T: HttpTransport, F: Future>(
+    transport: &T,
+    duration: Duration,
+    future: F,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut sleep = pin!(transport.sleep(duration));
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        sleep.as_mut().poll(cx).map(|_| None)
+    })
+    .await
+}
+
+/// Connections and timers of the tokio runtime, the default transport
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioTransport;
+
+#[cfg(feature = "tokio")]
+impl HttpTransport for TokioTransport {
+    type Connection = tokio::net::TcpStream;
+
+    async fn connect(&self, addr: &str) -> io::Result<Self::Connection> {
+        tokio::net::TcpStream::connect(addr).await
+    }
+
+    async fn read(&self, connection: &mut Self::Connection, buf: &mut [u8]) -> io::Result<usize> {
+        use tokio::io::AsyncReadExt;
+
+        connection.read(buf).await
+    }
+
+    async fn write_all(&self, connection: &mut Self::Connection, buf: &[u8]) -> io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        connection.write_all(buf).await?;
+        connection.flush().await
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+
+    fn is_open(&self, connection: &mut Self::Connection) -> bool {
+        let mut byte = [0u8; 1];
+        matches!(connection.try_read(&mut byte), Err(e) if e.kind() == io::ErrorKind::WouldBlock)
+    }
+}
+
+/// Default transport of [`HttpVmixClient`](crate::HttpVmixClient) without the
+/// `tokio` feature, which cannot be created
+///
+/// A client using another transport is made with
+/// [`new_with_transport`](crate::HttpVmixClient::new_with_transport).
+#[cfg(not(feature = "tokio"))]
+#[derive(Debug, Clone, Copy)]
+pub enum NoTransport {}
+
+#[cfg(not(feature = "tokio"))]
+impl HttpTransport for NoTransport {
+    type Connection = std::convert::Infallible;
+
+    async fn connect(&self, _addr: &str) -> io::Result<Self::Connection> {
+        match *self {}
+    }
+
+    async fn read(&self, _connection: &mut Self::Connection, _buf: &mut [u8]) -> io::Result<usize> {
+        match *self {}
+    }
+
+    async fn write_all(&self, _connection: &mut Self::Connection, _buf: &[u8]) -> io::Result<()> {
+        match *self {}
+    }
+
+    async fn sleep(&self, _duration: Duration) {
+        match *self {}
+    }
+
+    fn is_open(&self, _connection: &mut Self::Connection) -> bool {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "futures-io")]
+pub use self::generic_io::FuturesIoTransport;
+
+#[cfg(feature = "futures-io")]
+mod generic_io {
+    use super::HttpTransport;
+    use futures_io::{AsyncRead, AsyncWrite};
+    use std::{
+        fmt,
+        future::{Future, poll_fn},
+        io,
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Waker},
+        time::Duration,
+    };
+
+    type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+    /// Connections implementing the `futures-io` traits, for executors such as
+    /// async-std or smol
+    ///
+    /// Built from a function opening a connection and a function sleeping,
+    /// both taken from the executor. Whether an idle connection is still open
+    /// is checked by polling it once, so reading must return `Pending` instead
+    /// of blocking when nothing arrived, as the streams of async executors do.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use vmix_http::{FuturesIoTransport, HttpVmixClient};
+    ///
+    /// let transport = FuturesIoTransport::new(
+    ///     |addr| async move { smol::net::TcpStream::connect(addr).await },
+    ///     |duration| async move {
+    ///         smol::Timer::after(duration).await;
+    ///     },
+    /// );
+    /// let client = HttpVmixClient::new_with_transport("127.0.0.1", 8088, Duration::from_secs(5), transport);
+    /// ```
+    pub struct FuturesIoTransport<S> {
+        connect: Arc<dyn Fn(String) -> BoxFuture<io::Result<S>> + Send + Sync>,
+        sleep: Arc<dyn Fn(Duration) -> BoxFuture<()> + Send + Sync>,
+    }
+
+    impl<S> FuturesIoTransport<S>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        pub fn new<C, CF, W, WF>(connect: C, sleep: W) -> Self
+        where
+            C: Fn(String) -> CF + Send + Sync + 'static,
+            CF: Future<Output = io::Result<S>> + Send + 'static,
+            W: Fn(Duration) -> WF + Send + Sync + 'static,
+            WF: Future<Output = ()> + Send + 'static,
+        {
+            Self {
+                connect: Arc::new(move |addr| Box::pin(connect(addr))),
+                sleep: Arc::new(move |duration| Box::pin(sleep(duration))),
+            }
+        }
+    }
+
+    impl<S> Clone for FuturesIoTransport<S> {
+        fn clone(&self) -> Self {
+            Self {
+                connect: self.connect.clone(),
+                sleep: self.sleep.clone(),
+            }
+        }
+    }
+
+    impl<S> fmt::Debug for FuturesIoTransport<S> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("FuturesIoTransport").finish_non_exhaustive()
+        }
+    }
+
+    impl<S> HttpTransport for FuturesIoTransport<S>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        type Connection = S;
+
+        fn connect(&self, addr: &str) -> impl Future<Output = io::Result<S>> + Send {
+            (self.connect)(addr.to_string())
+        }
+
+        async fn read(&self, connection: &mut S, buf: &mut [u8]) -> io::Result<usize> {
+            poll_fn(|cx| Pin::new(&mut *connection).poll_read(cx, buf)).await
+        }
+
+        async fn write_all(&self, connection: &mut S, mut buf: &[u8]) -> io::Result<()> {
+            while !buf.is_empty() {
+                let n = poll_fn(|cx| Pin::new(&mut *connection).poll_write(cx, buf)).await?;
+                if n == 0 {
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                buf = &buf[n..];
+            }
+            poll_fn(|cx| Pin::new(&mut *connection).poll_flush(cx)).await
+        }
+
+        fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+            (self.sleep)(duration)
+        }
+
+        fn is_open(&self, connection: &mut S) -> bool {
+            // A read that would wait means there is nothing to read
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut byte = [0u8; 1];
+            Pin::new(connection)
+                .poll_read(&mut cx, &mut byte)
+                .is_pending()
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{client::HttpVmixClient, retry::random_up_to, transport::HttpTransport};
+use std::{
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{sync::broadcast, task::JoinHandle};
+use vmix_core::{StateChange, Vmix, diff};
+
+/// Settings of an [`HttpStateWatcher`]
+///
+/// # Example
+/// ```rust,ignore
+/// let config = WatchConfig::new(Duration::from_millis(500))
+///     .jitter(Duration::from_millis(100))
+///     .capacity(512);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    interval: Duration,
+    jitter: Duration,
+    capacity: usize,
+}
+
+impl WatchConfig {
+    /// Poll the XML state every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            jitter: Duration::ZERO,
+            capacity: 256,
+        }
+    }
+
+    /// Wait up to `jitter` longer than the interval, chosen at random for every poll
+    ///
+    /// Keeps several watchers started together from polling vMix at the same instant.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Number of events buffered for each subscriber
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(1))
+    }
+}
+
+/// Something an [`HttpStateWatcher`] noticed
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WatchEvent {
+    /// The state differs from the previous poll
+    Change(StateChange),
+    /// Polling failed, with the error; sent once until polling succeeds again
+    Unreachable(String),
+    /// Polling succeeds again after having failed
+    Reachable,
+}
+
+/// Change events for a vMix that is only reachable over HTTP
+///
+/// vMix pushes events over TCP only. The watcher polls the XML state every
+/// interval instead and compares each snapshot with the previous one, so the
+/// changes arrive with up to one interval of delay. Level meters are ignored.
+/// Changes that are undone between two polls are not seen.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_http::{HttpStateWatcher, HttpVmixClient, WatchConfig, WatchEvent};
+/// use vmix_http::vmix_core::StateChange;
+///
+/// let watcher = HttpStateWatcher::start(client, WatchConfig::new(Duration::from_millis(500)));
+/// let mut events = watcher.subscribe();
+/// while let Ok(event) = events.recv().await {
+///     if let WatchEvent::Change(StateChange::Active { mix: 1, input }) = event {
+///         println!("input {} is live", input);
+///     }
+/// }
+/// ```
+pub struct HttpStateWatcher {
+    state: Arc<RwLock<Option<Vmix>>>,
+    reachable: Arc<AtomicBool>,
+    events: broadcast::Sender<WatchEvent>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for HttpStateWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl HttpStateWatcher {
+    /// Start polling on the current tokio runtime, the first poll immediately
+    ///
+    /// The first snapshot only sets the baseline; events are sent for the
+    /// differences found by the following polls.
+    pub fn start<T: HttpTransport>(client: HttpVmixClient<T>, config: WatchConfig) -> Self {
+        let state = Arc::new(RwLock::new(None));
+        let reachable = Arc::new(AtomicBool::new(false));
+        let (events, _) = broadcast::channel(config.capacity);
+
+        let poller = Poller {
+            client,
+            state: state.clone(),
+            reachable: reachable.clone(),
+            events: events.clone(),
+            config,
+        };
+        let task = tokio::spawn(poller.run());
+
+        Self {
+            state,
+            reachable,
+            events,
+            task,
+        }
+    }
+
+    /// Receive the events sent from now on
+    ///
+    /// A subscriber that falls more than the configured capacity behind misses
+    /// the oldest events and is told how many with `RecvError::Lagged`.
+    pub fn subscribe(&self) -> broadcast::Receiver<WatchEvent> {
+        self.events.subscribe()
+    }
+
+    /// The state read by the latest successful poll
+    pub fn snapshot(&self) -> Option<Vmix> {
+        self.read(Clone::clone)
+    }
+
+    /// Run `f` on the state read by the latest successful poll, without cloning it
+    pub fn read<R>(&self, f: impl FnOnce(&Vmix) -> R) -> Option<R> {
+        let state = match self.state.read() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        state.as_ref().map(f)
+    }
+
+    /// Whether the latest poll succeeded
+    pub fn is_reachable(&self) -> bool {
+        self.reachable.load(Ordering::SeqCst)
+    }
+
+    /// Whether the state is still being polled
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+}
+
+struct Poller<T: HttpTransport> {
+    client: HttpVmixClient<T>,
+    state: Arc<RwLock<Option<Vmix>>>,
+    reachable: Arc<AtomicBool>,
+    events: broadcast::Sender<WatchEvent>,
+    config: WatchConfig,
+}
+
+impl<T: HttpTransport> Poller<T> {
+    async fn run(self) {
+        // Unknown until the first poll, so the first success is not reported as a recovery
+        let mut reachable = None;
+
+        loop {
+            match self.client.get_xml_state().await {
+                Ok(new) => {
+                    if reachable == Some(false) {
+                        self.send(WatchEvent::Reachable);
+                    }
+                    reachable = Some(true);
+                    self.reachable.store(true, Ordering::SeqCst);
+
+                    let previous = match self.state.write() {
+                        Ok(mut state) => state.replace(new.clone()),
+                        Err(poisoned) => poisoned.into_inner().replace(new.clone()),
+                    };
+                    if let Some(previous) = previous {
+                        for change in diff(&previous, &new) {
+                            self.send(WatchEvent::Change(change));
+                        }
+                    }
+                }
+                Err(err) => {
+                    if reachable != Some(false) {
+                        self.send(WatchEvent::Unreachable(err.to_string()));
+                    }
+                    reachable = Some(false);
+                    self.reachable.store(false, Ordering::SeqCst);
+                }
+            }
+
+            tokio::time::sleep(self.config.interval + random_up_to(self.config.jitter)).await;
+        }
+    }
+
+    fn send(&self, event: WatchEvent) {
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+}
@@ -6,7 +6,7 @@ pub use vmix_core;
 pub use vmix_tcp;
 
 // Re-export vmix-http when enabled
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "futures-io", feature = "blocking"))]
 pub use vmix_http;
 
 // Convenience re-exports for common types
@@ -19,8 +19,24 @@ pub mod commands {
     pub use vmix_tcp::commands::*;
 }
 
+#[cfg(feature = "tcp")]
+pub mod health {
+    pub use vmix_tcp::health::*;
+}
+
+#[cfg(feature = "tcp")]
+pub mod live_state {
+    pub use vmix_tcp::live_state::*;
+}
+
+#[cfg(feature = "tcp")]
+pub mod tally {
+    pub use vmix_tcp::tally::*;
+}
+
 #[cfg(feature = "tcp")]
 pub mod acts {
+    pub use vmix_tcp::activator_state::*;
     pub use vmix_tcp::acts::*;
 }
 
@@ -32,19 +48,50 @@ pub mod traits {
     pub use vmix_http::traits::*;
 }
 
+/// Async clients and the transport-agnostic `VmixClient` trait
+#[cfg(feature = "async")]
+pub mod client {
+    pub use vmix_tcp::async_api::*;
+    pub use vmix_tcp::client::*;
+}
+
 #[cfg(feature = "tcp")]
 pub mod vmix {
     pub use vmix_tcp::vmix::*;
 }
 
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "futures-io"))]
 pub mod http {
     pub use vmix_http::client::*;
+    pub use vmix_http::transport::*;
+    #[cfg(feature = "http")]
+    pub use vmix_http::watcher::*;
+}
+
+/// HTTP client for applications without an async runtime
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    pub use vmix_http::blocking::*;
 }
 
 // Top-level convenience re-exports for backward compatibility
+#[cfg(any(
+    feature = "std",
+    feature = "tcp",
+    feature = "http",
+    feature = "futures-io",
+    feature = "blocking"
+))]
+pub use vmix_core::VmixError;
+
 #[cfg(feature = "tcp")]
 pub use vmix_tcp::{VmixApi, VmixTcpApiClient};
 
-#[cfg(feature = "http")]
+#[cfg(feature = "async")]
+pub use vmix_tcp::{AsyncVmixApi, VmixClient};
+
+#[cfg(any(feature = "http", feature = "futures-io"))]
 pub use vmix_http::{HttpVmixClient, VmixApiClient};
+
+#[cfg(feature = "blocking")]
+pub use vmix_http::BlockingHttpVmixClient;
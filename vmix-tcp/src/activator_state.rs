@@ -0,0 +1,403 @@
+use crate::{
+    acts::{ActivatorsData, Bus},
+    commands::{ActivatorsResponse, InputNumber, RecvCommand},
+};
+use std::collections::{BTreeSet, HashMap};
+use vmix_core::{AudioBus, State, Vmix};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MixState {
+    live: BTreeSet<InputNumber>,
+    preview: BTreeSet<InputNumber>,
+}
+
+/// Current vMix state as reported by activator events
+///
+/// Feed it every `ACTS` event received after `SUBSCRIBE ACTS` and query it
+/// instead of keeping separate maps per application. Values that vMix has not
+/// reported yet are `None`. Volumes use the 0.0–1.0 range of activator events.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_tcp::{ActivatorState, EventFilter, EventKind, acts::Bus};
+///
+/// let mut state = ActivatorState::from_snapshot(&vmix_core::from_str(&api.fetch_xml(timeout)?.body)?);
+/// let acts = api.subscribe(EventFilter::only(&[EventKind::Acts]), 256);
+/// while let Ok(event) = acts.recv() {
+///     state.apply_event(&event);
+///     println!("live on mix 3: {:?}, bus C: {:?}", state.live_inputs(3), state.bus_volume(Bus::C));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivatorState {
+    mixes: HashMap<u8, MixState>,
+    playing: BTreeSet<InputNumber>,
+    input_volume: HashMap<InputNumber, f32>,
+    input_headphones: HashMap<InputNumber, f32>,
+    input_audio: HashMap<InputNumber, bool>,
+    input_solo: HashMap<InputNumber, bool>,
+    input_master_audio: HashMap<InputNumber, bool>,
+    input_bus_audio: HashMap<(InputNumber, Bus), bool>,
+    master_volume: Option<f32>,
+    master_headphones: Option<f32>,
+    master_audio: Option<bool>,
+    bus_volume: HashMap<Bus, f32>,
+    bus_audio: HashMap<Bus, bool>,
+    bus_solo: HashMap<Bus, bool>,
+    overlays: HashMap<u8, InputNumber>,
+    fade_to_black: Option<bool>,
+    recording: Option<bool>,
+    streaming: Option<bool>,
+    external: Option<bool>,
+    fullscreen: Option<bool>,
+    replay_playing: Option<bool>,
+}
+
+fn set_member(set: &mut BTreeSet<InputNumber>, input: InputNumber, member: bool) {
+    if member {
+        set.insert(input);
+    } else {
+        set.remove(&input);
+    }
+}
+
+fn parse_input(number: &str) -> Option<InputNumber> {
+    number.trim().parse().ok()
+}
+
+impl ActivatorState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the state from a full XML snapshot
+    pub fn from_snapshot(vmix: &Vmix) -> Self {
+        let mut state = Self::new();
+        state.apply_snapshot(vmix);
+        state
+    }
+
+    /// Replace the current state with the contents of a full XML snapshot
+    ///
+    /// Volumes are converted from the 0–100 range used in the XML.
+    pub fn apply_snapshot(&mut self, vmix: &Vmix) {
+        *self = Self::default();
+
+        let main_mix = (1, vmix.active.as_str(), vmix.preview.as_str());
+        let other_mixes = vmix.mix.iter().filter_map(|mix| {
+            let number = mix.number.trim().parse::<u8>().ok()?;
+            Some((number, mix.active.as_str(), mix.preview.as_str()))
+        });
+        for (number, live, preview) in std::iter::once(main_mix).chain(other_mixes) {
+            let mix = self.mixes.entry(number).or_default();
+            mix.live.extend(parse_input(live));
+            mix.preview.extend(parse_input(preview));
+        }
+
+        for input in &vmix.inputs.input {
+            let Some(number) = parse_input(&input.number) else {
+                continue;
+            };
+            if matches!(input.state, State::Running) {
+                self.playing.insert(number);
+            }
+            if let Some(volume) = input.volume {
+                self.input_volume.insert(number, (volume / 100.0) as f32);
+            }
+            if let Some(muted) = input.muted {
+                self.input_audio.insert(number, !muted);
+            }
+            if let Some(solo) = input.solo {
+                self.input_solo.insert(number, solo);
+            }
+            if let Some(busses) = &input.audiobusses {
+                let busses: Vec<&str> = busses.split(',').map(str::trim).collect();
+                self.input_master_audio
+                    .insert(number, busses.contains(&"M"));
+                for bus in Bus::ALL {
+                    let routed = busses.iter().any(|b| b.parse::<Bus>().ok() == Some(bus));
+                    self.input_bus_audio.insert((number, bus), routed);
+                }
+            }
+        }
+
+        for overlay in &vmix.overlays.overlay {
+            let number = overlay.number.trim().parse::<u8>().ok();
+            let input = overlay.input.as_deref().and_then(parse_input);
+            if let (Some(number), Some(input)) = (number, input) {
+                self.overlays.insert(number, input);
+            }
+        }
+
+        let master = &vmix.audio.master;
+        self.master_volume = Some((master.volume / 100.0) as f32);
+        self.master_headphones = master.headphones_volume.map(|v| (v / 100.0) as f32);
+        self.master_audio = Some(!master.muted);
+
+        let audio = &vmix.audio;
+        let busses: [(Bus, &Option<AudioBus>); 7] = [
+            (Bus::A, &audio.bus_a),
+            (Bus::B, &audio.bus_b),
+            (Bus::C, &audio.bus_c),
+            (Bus::D, &audio.bus_d),
+            (Bus::E, &audio.bus_e),
+            (Bus::F, &audio.bus_f),
+            (Bus::G, &audio.bus_g),
+        ];
+        for (bus, audio_bus) in busses {
+            if let Some(audio_bus) = audio_bus {
+                self.bus_volume
+                    .insert(bus, (audio_bus.volume / 100.0) as f32);
+                self.bus_audio.insert(bus, !audio_bus.muted);
+                if let Some(solo) = audio_bus.solo {
+                    self.bus_solo.insert(bus, solo);
+                }
+            }
+        }
+
+        self.fade_to_black = Some(vmix.fade_to_black);
+        self.recording = Some(vmix.recording);
+        self.streaming = Some(vmix.streaming);
+        self.external = Some(vmix.external);
+        self.fullscreen = Some(vmix.fullscreen);
+    }
+
+    /// Apply a single activator change
+    pub fn apply(&mut self, data: &ActivatorsData) {
+        match data {
+            ActivatorsData::InputMix { mix, input, active } => {
+                set_member(
+                    &mut self.mixes.entry(*mix).or_default().live,
+                    *input,
+                    *active,
+                );
+            }
+            ActivatorsData::InputPreviewMix { mix, input, active } => {
+                set_member(
+                    &mut self.mixes.entry(*mix).or_default().preview,
+                    *input,
+                    *active,
+                );
+            }
+            ActivatorsData::InputPlaying(input, active) => {
+                set_member(&mut self.playing, *input, *active);
+            }
+            ActivatorsData::InputVolume(input, level) => {
+                self.input_volume.insert(*input, *level);
+            }
+            ActivatorsData::InputHeadphones(input, level) => {
+                self.input_headphones.insert(*input, *level);
+            }
+            ActivatorsData::MasterVolume(level) => self.master_volume = Some(*level),
+            ActivatorsData::MasterHeadphones(level) => self.master_headphones = Some(*level),
+            ActivatorsData::BusVolume { bus, level } => {
+                self.bus_volume.insert(*bus, *level);
+            }
+            ActivatorsData::InputAudio(input, active) => {
+                self.input_audio.insert(*input, *active);
+            }
+            ActivatorsData::InputSolo(input, active) => {
+                self.input_solo.insert(*input, *active);
+            }
+            ActivatorsData::InputBusAudio { input, bus, active } => {
+                self.input_bus_audio.insert((*input, *bus), *active);
+            }
+            ActivatorsData::InputMasterAudio(input, active) => {
+                self.input_master_audio.insert(*input, *active);
+            }
+            ActivatorsData::MasterAudio(active) => self.master_audio = Some(*active),
+            ActivatorsData::BusAudio { bus, active } => {
+                self.bus_audio.insert(*bus, *active);
+            }
+            ActivatorsData::BusSolo { bus, active } => {
+                self.bus_solo.insert(*bus, *active);
+            }
+            ActivatorsData::FadeToBlack(active) => self.fade_to_black = Some(*active),
+            ActivatorsData::Recording(active) => self.recording = Some(*active),
+            ActivatorsData::Streaming(active) => self.streaming = Some(*active),
+            ActivatorsData::External(active) => self.external = Some(*active),
+            ActivatorsData::Fullscreen(active) => self.fullscreen = Some(*active),
+            ActivatorsData::Overlay {
+                overlay,
+                input,
+                active,
+            } => {
+                if *active {
+                    self.overlays.insert(*overlay, *input);
+                } else if self.overlays.get(overlay) == Some(input) {
+                    self.overlays.remove(overlay);
+                }
+            }
+            ActivatorsData::ReplayPlaying(active) => self.replay_playing = Some(*active),
+            ActivatorsData::Unknown(_) => {}
+        }
+    }
+
+    /// Apply an `ACTS` response
+    pub fn apply_response(&mut self, response: &ActivatorsResponse) {
+        self.apply(&response.body);
+    }
+
+    /// Apply an event if it is an `ACTS` event, returning whether it was
+    pub fn apply_event(&mut self, event: &RecvCommand) -> bool {
+        match event {
+            RecvCommand::ACTS(response) => {
+                self.apply_response(response);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mix numbers for which live or preview inputs have been reported
+    pub fn mixes(&self) -> Vec<u8> {
+        let mut mixes: Vec<u8> = self.mixes.keys().copied().collect();
+        mixes.sort_unstable();
+        mixes
+    }
+
+    /// Inputs currently live on `mix`, where mix 1 is the main output
+    pub fn live_inputs(&self, mix: u8) -> Vec<InputNumber> {
+        self.mixes
+            .get(&mix)
+            .map(|m| m.live.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Inputs currently in preview on `mix`
+    pub fn preview_inputs(&self, mix: u8) -> Vec<InputNumber> {
+        self.mixes
+            .get(&mix)
+            .map(|m| m.preview.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether `input` is live on `mix`
+    pub fn is_live(&self, mix: u8, input: InputNumber) -> bool {
+        self.mixes
+            .get(&mix)
+            .is_some_and(|m| m.live.contains(&input))
+    }
+
+    /// Whether `input` is in preview on `mix`
+    pub fn is_preview(&self, mix: u8, input: InputNumber) -> bool {
+        self.mixes
+            .get(&mix)
+            .is_some_and(|m| m.preview.contains(&input))
+    }
+
+    /// Inputs that are currently playing
+    pub fn playing_inputs(&self) -> Vec<InputNumber> {
+        self.playing.iter().copied().collect()
+    }
+
+    /// Whether `input` is currently playing
+    pub fn is_playing(&self, input: InputNumber) -> bool {
+        self.playing.contains(&input)
+    }
+
+    pub fn input_volume(&self, input: InputNumber) -> Option<f32> {
+        self.input_volume.get(&input).copied()
+    }
+
+    pub fn input_headphones(&self, input: InputNumber) -> Option<f32> {
+        self.input_headphones.get(&input).copied()
+    }
+
+    /// Whether the audio of `input` is on
+    pub fn input_audio(&self, input: InputNumber) -> Option<bool> {
+        self.input_audio.get(&input).copied()
+    }
+
+    pub fn input_solo(&self, input: InputNumber) -> Option<bool> {
+        self.input_solo.get(&input).copied()
+    }
+
+    /// Whether `input` is routed to the master bus
+    pub fn input_master_audio(&self, input: InputNumber) -> Option<bool> {
+        self.input_master_audio.get(&input).copied()
+    }
+
+    /// Whether `input` is routed to `bus`
+    pub fn input_bus_audio(&self, input: InputNumber, bus: Bus) -> Option<bool> {
+        self.input_bus_audio.get(&(input, bus)).copied()
+    }
+
+    pub fn master_volume(&self) -> Option<f32> {
+        self.master_volume
+    }
+
+    pub fn master_headphones(&self) -> Option<f32> {
+        self.master_headphones
+    }
+
+    pub fn master_audio(&self) -> Option<bool> {
+        self.master_audio
+    }
+
+    pub fn bus_volume(&self, bus: Bus) -> Option<f32> {
+        self.bus_volume.get(&bus).copied()
+    }
+
+    pub fn bus_audio(&self, bus: Bus) -> Option<bool> {
+        self.bus_audio.get(&bus).copied()
+    }
+
+    pub fn bus_solo(&self, bus: Bus) -> Option<bool> {
+        self.bus_solo.get(&bus).copied()
+    }
+
+    /// Input currently shown on overlay channel `overlay`
+    pub fn overlay_input(&self, overlay: u8) -> Option<InputNumber> {
+        self.overlays.get(&overlay).copied()
+    }
+
+    pub fn fade_to_black(&self) -> Option<bool> {
+        self.fade_to_black
+    }
+
+    pub fn recording(&self) -> Option<bool> {
+        self.recording
+    }
+
+    pub fn streaming(&self) -> Option<bool> {
+        self.streaming
+    }
+
+    pub fn external(&self) -> Option<bool> {
+        self.external
+    }
+
+    pub fn fullscreen(&self) -> Option<bool> {
+        self.fullscreen
+    }
+
+    pub fn replay_playing(&self) -> Option<bool> {
+        self.replay_playing
+    }
+}
+
+impl Extend<ActivatorsData> for ActivatorState {
+    fn extend<I: IntoIterator<Item = ActivatorsData>>(&mut self, iter: I) {
+        for data in iter {
+            self.apply(&data);
+        }
+    }
+}
+
+impl<'a> Extend<&'a ActivatorsData> for ActivatorState {
+    fn extend<I: IntoIterator<Item = &'a ActivatorsData>>(&mut self, iter: I) {
+        for data in iter {
+            self.apply(data);
+        }
+    }
+}
+
+impl FromIterator<ActivatorsData> for ActivatorState {
+    /// Seed the state from a burst of `ACTS` query responses
+    fn from_iter<I: IntoIterator<Item = ActivatorsData>>(iter: I) -> Self {
+        let mut state = Self::new();
+        state.extend(iter);
+        state
+    }
+}
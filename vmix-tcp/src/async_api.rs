@@ -0,0 +1,157 @@
+use crate::{
+    client::{ClientCapabilities, Transport, VmixClient},
+    commands::{
+        FunctionResponse, InputNumber, SendCommand, TallyData, XMLResponse, XMLTextResponse,
+    },
+    events::{EventFilter, EventSubscription},
+    tally::TallyTracker,
+    traits::AsyncVmixTcpApiClient,
+    vmix::VmixApi,
+};
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use vmix_core::{Vmix, VmixFunction, error::Result};
+
+/// Async handle to a [`VmixApi`] connection
+///
+/// The TCP client runs on its own threads; the blocking request methods are
+/// moved to the tokio blocking pool, so every request waits for at most
+/// `timeout` without blocking the async runtime. Cloning shares the connection.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_tcp::{AsyncVmixApi, AsyncVmixTcpApiClient, VmixApi, VmixFunction};
+///
+/// let api = AsyncVmixApi::from(VmixApi::new(addr, Duration::from_secs(5))?);
+/// api.execute(&VmixFunction::cut()).await?;
+/// let xml = api.fetch_xml().await?;
+/// ```
+#[derive(Clone)]
+pub struct AsyncVmixApi {
+    api: Arc<VmixApi>,
+    timeout: Duration,
+}
+
+impl AsyncVmixApi {
+    pub fn new(api: Arc<VmixApi>) -> Self {
+        Self {
+            api,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// How long requests wait for their response
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The underlying blocking client
+    pub fn api(&self) -> &Arc<VmixApi> {
+        &self.api
+    }
+
+    async fn blocking<R, F>(&self, f: F) -> Result<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&VmixApi, Duration) -> Result<R> + Send + 'static,
+    {
+        let api = self.api.clone();
+        let timeout = self.timeout;
+        match tokio::task::spawn_blocking(move || f(&api, timeout)).await {
+            Ok(result) => result,
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+}
+
+impl From<VmixApi> for AsyncVmixApi {
+    fn from(api: VmixApi) -> Self {
+        Self::new(Arc::new(api))
+    }
+}
+
+#[async_trait]
+impl AsyncVmixTcpApiClient for AsyncVmixApi {
+    async fn send_command(&self, command: SendCommand) -> Result<()> {
+        // Queueing blocks while the queue is full
+        self.blocking(move |api, _| api.send_command(command)).await
+    }
+
+    async fn call_function(&self, function: &str, query: Option<&str>) -> Result<FunctionResponse> {
+        let function = function.to_string();
+        let query = query.map(str::to_string);
+        self.blocking(move |api, timeout| api.call_function(&function, query.as_deref(), timeout))
+            .await
+    }
+
+    async fn execute(&self, function: &VmixFunction) -> Result<FunctionResponse> {
+        let function = function.clone();
+        self.blocking(move |api, timeout| api.execute(&function, timeout))
+            .await
+    }
+
+    async fn query_xmltext(&self, path: &str) -> Result<XMLTextResponse> {
+        let path = path.to_string();
+        self.blocking(move |api, timeout| api.query_xmltext(&path, timeout))
+            .await
+    }
+
+    async fn fetch_xml(&self) -> Result<XMLResponse> {
+        self.blocking(|api, timeout| api.fetch_xml(timeout)).await
+    }
+
+    fn subscribe(&self, filter: EventFilter, capacity: usize) -> EventSubscription {
+        self.api.subscribe(filter, capacity)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.api.is_connected()
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        self.api.disconnect()
+    }
+}
+
+#[async_trait]
+impl VmixClient for AsyncVmixApi {
+    fn transport_capabilities(&self) -> ClientCapabilities {
+        ClientCapabilities {
+            transport: Transport::Tcp,
+            events: true,
+            function_responses: true,
+        }
+    }
+
+    async fn execute(&self, function: &VmixFunction) -> Result<()> {
+        AsyncVmixTcpApiClient::execute(self, function).await?;
+        Ok(())
+    }
+
+    async fn fetch_state(&self) -> Result<Vmix> {
+        let response = self.fetch_xml().await?;
+        Ok(vmix_core::from_str(&response.body)?)
+    }
+
+    async fn tally(&self) -> Result<HashMap<InputNumber, TallyData>> {
+        let state = self.fetch_state().await?;
+        // Every input of the snapshot, as over HTTP: OFF unless live or in preview
+        let mut tally: HashMap<InputNumber, TallyData> = state
+            .inputs
+            .input
+            .iter()
+            .filter_map(|input| Some((input.number.trim().parse().ok()?, TallyData::OFF)))
+            .collect();
+        tally.extend(TallyTracker::from_snapshot(&state).tally(1));
+        Ok(tally)
+    }
+
+    async fn subscribe(&self, filter: EventFilter, capacity: usize) -> Result<EventSubscription> {
+        Ok(self.api.subscribe(filter, capacity))
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.api.is_connected()
+    }
+}
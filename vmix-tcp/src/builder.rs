@@ -0,0 +1,192 @@
+use crate::{
+    commands::SUBSCRIBECommand,
+    queue::{CommandPriority, QueuePolicy},
+    vmix::VmixApi,
+};
+use std::{net::SocketAddr, time::Duration};
+use vmix_core::error::Result;
+
+/// Builder for [`VmixApi`] connections
+///
+/// Every setting has a default matching `VmixApi::new`, so only the values
+/// that need tuning have to be specified.
+///
+/// # Example
+/// ```rust,ignore
+/// use std::time::Duration;
+/// use vmix_tcp::{SUBSCRIBECommand, VmixApi};
+///
+/// let vmix = VmixApi::builder("192.168.1.10:8099".parse()?)
+///     .connect_timeout(Duration::from_secs(3))
+///     .read_timeout(Duration::from_secs(10))
+///     .event_buffer(1024)
+///     .rate_limit(Some(50))
+///     .tcp_keepalive(Some(Duration::from_secs(15)))
+///     .subscribe(SUBSCRIBECommand::TALLY)
+///     .subscribe(SUBSCRIBECommand::ACTS)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct VmixApiBuilder {
+    pub(crate) remote: SocketAddr,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) read_timeout: Duration,
+    pub(crate) write_timeout: Duration,
+    pub(crate) xml_read_timeout: Duration,
+    pub(crate) shutdown_timeout: Duration,
+    pub(crate) command_buffer: usize,
+    pub(crate) event_buffer: usize,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) tcp_nodelay: bool,
+    pub(crate) subscriptions: Vec<SUBSCRIBECommand>,
+    pub(crate) queue_policy: QueuePolicy,
+}
+
+impl VmixApiBuilder {
+    pub fn new(remote: SocketAddr) -> Self {
+        let timeout = Duration::from_secs(5);
+        Self {
+            remote,
+            connect_timeout: timeout,
+            read_timeout: timeout,
+            write_timeout: timeout,
+            xml_read_timeout: crate::commands::DEFAULT_XML_READ_TIMEOUT,
+            shutdown_timeout: Duration::from_millis(500),
+            command_buffer: 64,
+            event_buffer: 64,
+            tcp_keepalive: None,
+            tcp_nodelay: false,
+            subscriptions: Vec::new(),
+            queue_policy: QueuePolicy::default(),
+        }
+    }
+
+    /// Set the connect, read and write timeouts at once
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self.read_timeout = timeout;
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Maximum time to wait for the TCP connection to be established
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Read timeout applied to the socket
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Write timeout applied to the socket
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Maximum time to wait for the body of an `XML` response once its header arrived
+    pub fn xml_read_timeout(mut self, timeout: Duration) -> Self {
+        self.xml_read_timeout = timeout;
+        self
+    }
+
+    /// How long dropping the client waits for the reader and writer threads to exit
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Number of commands waiting to be written before queueing more blocks (64 by default)
+    ///
+    /// Once that many are queued, [`VmixApi::send_command`] and sending on
+    /// [`VmixApi::sender`] wait for the writer thread to take one, and requests
+    /// such as `call_function` fail with a timeout if it does not take one in
+    /// time. A command replacing a queued one for the same control, see
+    /// [`coalesce`](Self::coalesce), never waits.
+    pub fn command_buffer(mut self, capacity: usize) -> Self {
+        self.command_buffer = capacity;
+        self
+    }
+
+    /// Number of events buffered for `try_receive_command` before the oldest are dropped
+    pub fn event_buffer(mut self, capacity: usize) -> Self {
+        self.event_buffer = capacity;
+        self
+    }
+
+    /// Enable TCP keepalive probes after the connection has been idle for `idle`
+    pub fn tcp_keepalive(mut self, idle: Option<Duration>) -> Self {
+        self.tcp_keepalive = idle;
+        self
+    }
+
+    /// Disable Nagle's algorithm so small commands are sent immediately
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.tcp_nodelay = nodelay;
+        self
+    }
+
+    /// Send a `SUBSCRIBE` command as soon as the connection is established
+    pub fn subscribe(mut self, command: SUBSCRIBECommand) -> Self {
+        if !self.subscriptions.contains(&command) {
+            self.subscriptions.push(command);
+        }
+        self
+    }
+
+    /// Write at most `per_second` commands per second, or as fast as possible with `None`
+    ///
+    /// Bursts of up to `per_second` commands are sent without delay.
+    pub fn rate_limit(mut self, per_second: Option<u32>) -> Self {
+        self.queue_policy.rate_limit = per_second;
+        self
+    }
+
+    /// Send switching functions (`Cut`, `Fade`, transitions, overlays) before other
+    /// queued commands, and continuous controls such as `SetVolume` after them
+    ///
+    /// Disabled by default. Enabling it lets a switch overtake a command queued
+    /// before it, e.g. an `OverlayInput1In` going on air before the `SetText`
+    /// meant for it, and lets a busy queue hold back fader updates indefinitely.
+    pub fn prioritize_switching(mut self, prioritize: bool) -> Self {
+        self.queue_policy.prioritize_switching = prioritize;
+        self
+    }
+
+    /// Override the priority of `function`
+    ///
+    /// Commands are written in the order they were queued, except that a
+    /// command of a higher [`CommandPriority`] is written before every queued
+    /// command of a lower one, whatever input they are for. Lower priority
+    /// commands wait as long as higher priority ones keep being queued. Every
+    /// function is `Normal` by default, so the queue is first in, first out
+    /// until priorities are set here or with
+    /// [`prioritize_switching`](Self::prioritize_switching).
+    pub fn function_priority(mut self, function: &str, priority: CommandPriority) -> Self {
+        self.queue_policy
+            .priorities
+            .insert(function.to_string(), priority);
+        self
+    }
+
+    /// Whether a queued `function` for an input is replaced by a newer one for the same input
+    ///
+    /// Enabled by default for faders and other continuous controls such as `SetVolume`,
+    /// `SetBalance`, `SetGain` and the bus volumes. The newer command is queued
+    /// behind every command queued before it, so a fader update sent after a
+    /// `Cut` is still written after the `Cut`.
+    pub fn coalesce(mut self, function: &str, coalesce: bool) -> Self {
+        self.queue_policy
+            .coalesce
+            .insert(function.to_string(), coalesce);
+        self
+    }
+
+    /// Connect to vMix with this configuration
+    pub fn build(self) -> Result<VmixApi> {
+        VmixApi::connect(self)
+    }
+}
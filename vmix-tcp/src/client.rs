@@ -0,0 +1,63 @@
+use crate::{
+    commands::{InputNumber, TallyData},
+    events::{EventFilter, EventSubscription},
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use vmix_core::{Vmix, VmixFunction, error::Result};
+
+/// Protocol a [`VmixClient`] talks to vMix with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Tcp,
+    Http,
+}
+
+/// What a [`VmixClient`] can do over its transport
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientCapabilities {
+    pub transport: Transport,
+    /// Whether [`VmixClient::subscribe`] delivers events pushed by vMix
+    pub events: bool,
+    /// Whether vMix reports if a function succeeded, rather than just receiving it
+    pub function_responses: bool,
+}
+
+/// vMix client independent of the transport
+///
+/// The trait does not depend on an async runtime, so it can be implemented and
+/// used on any executor. Implemented by the TCP client ([`AsyncVmixApi`](crate::AsyncVmixApi)) and the
+/// HTTP client, so code written against this trait can switch transports by
+/// configuration. Operations a transport cannot perform fail with
+/// [`VmixError::TransportUnsupported`](vmix_core::VmixError::TransportUnsupported);
+/// check [`transport_capabilities`](Self::transport_capabilities) up front.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_tcp::{VmixClient, VmixFunction};
+///
+/// async fn go_live(client: &dyn VmixClient, input: u16) -> vmix_tcp::vmix_core::error::Result<()> {
+///     client.execute(&VmixFunction::ActiveInput { input: input.into(), mix: None }).await
+/// }
+/// ```
+#[async_trait]
+pub trait VmixClient: Send + Sync {
+    fn transport_capabilities(&self) -> ClientCapabilities;
+
+    /// Execute a typed vMix function
+    async fn execute(&self, function: &VmixFunction) -> Result<()>;
+
+    /// Fetch the complete vMix state
+    async fn fetch_state(&self) -> Result<Vmix>;
+
+    /// Tally of every input on the main output
+    async fn tally(&self) -> Result<HashMap<InputNumber, TallyData>>;
+
+    /// Receive the events matching `filter`, buffering up to `capacity` of them
+    ///
+    /// Await events with [`Subscription::recv_async`](crate::Subscription::recv_async).
+    async fn subscribe(&self, filter: EventFilter, capacity: usize) -> Result<EventSubscription>;
+
+    /// Whether vMix is reachable
+    async fn is_connected(&self) -> bool;
+}
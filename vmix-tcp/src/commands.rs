@@ -7,10 +7,19 @@ use std::{
     net::TcpStream,
     time::Duration,
 };
+use vmix_core::{
+    VmixFunction,
+    error::{Result, VmixError},
+    query::FunctionParams,
+    version::VmixVersion,
+};
 
 pub type InputNumber = u16; // 0~1000
 
-#[derive(Debug)]
+/// Default time allowed for the body of an `XML` response to arrive
+pub const DEFAULT_XML_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
 pub enum Status {
     OK,             // "OK"
     ER,             // "ER"
@@ -35,12 +44,12 @@ impl From<String> for Status {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TallyResponse {
     pub status: Status,
     pub body: HashMap<InputNumber, TallyData>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TallyData {
     OFF,
     PROGRAM,
@@ -57,45 +66,52 @@ impl From<char> for TallyData {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FunctionResponse {
     pub status: Status,
     pub body: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct XMLResponse {
     pub status: Status,
     pub body: String, // Change to String for raw XML content
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct XMLTextResponse {
     pub status: Status,
     pub body: Option<String>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SubscribeResponse {
     pub status: Status,
     pub body: Option<String>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UnsubscribeResponse {
     pub status: Status,
     pub body: Option<String>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VersionResponse {
     pub status: Status,
-    pub version: Option<String>, // TODO: parse semver
+    pub version: Option<String>,
+}
+
+impl VersionResponse {
+    /// The reported version, or `None` if it is missing or not a version number
+    pub fn parsed(&self) -> Option<VmixVersion> {
+        self.version.as_deref()?.parse().ok()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActivatorsResponse {
     pub status: Status,
     pub body: ActivatorsData,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum RecvCommand {
     TALLY(TallyResponse),
     FUNCTION(FunctionResponse),
@@ -163,13 +179,54 @@ unsafe impl Sync for SUBSCRIBECommand {}
 unsafe impl Send for Status {}
 unsafe impl Sync for Status {}
 
+impl SendCommand {
+    /// `FUNCTION` command with percent-encoded parameters
+    ///
+    /// The query string is encoded the same way as the HTTP client's, so values may
+    /// contain spaces, `&` or non-ASCII text. `FUNCTION(name, query)` sends `query`
+    /// verbatim and expects it to be encoded already.
+    pub fn function(name: &str, params: &FunctionParams) -> Self {
+        let query = params.to_query_string();
+        SendCommand::FUNCTION(name.to_string(), (!query.is_empty()).then_some(query))
+    }
+
+    /// Name of the command as sent on the wire
+    pub fn name(&self) -> &'static str {
+        match self {
+            SendCommand::TALLY => "TALLY",
+            SendCommand::FUNCTION(_, _) => "FUNCTION",
+            SendCommand::ACTS(_, _) => "ACTS",
+            SendCommand::XML => "XML",
+            SendCommand::XMLTEXT(_) => "XMLTEXT",
+            SendCommand::SUBSCRIBE(_) => "SUBSCRIBE",
+            SendCommand::UNSUBSCRIBE(_) => "UNSUBSCRIBE",
+            SendCommand::QUIT => "QUIT",
+            SendCommand::VERSION => "VERSION",
+            SendCommand::RAW(_) => "RAW",
+        }
+    }
+}
+
+impl From<&VmixFunction> for SendCommand {
+    fn from(function: &VmixFunction) -> Self {
+        SendCommand::function(&function.name(), &function.params())
+    }
+}
+
+impl From<VmixFunction> for SendCommand {
+    fn from(function: VmixFunction) -> Self {
+        (&function).into()
+    }
+}
+
 impl From<SendCommand> for Vec<u8> {
     fn from(command: SendCommand) -> Self {
         match command {
             SendCommand::TALLY => "TALLY\r\n".as_bytes().to_vec(),
-            SendCommand::FUNCTION(func, query) => {
-                format!("FUNCTION {} {}\r\n", func, query.unwrap_or("".to_string())).into_bytes()
+            SendCommand::FUNCTION(func, Some(query)) if !query.is_empty() => {
+                format!("FUNCTION {} {}\r\n", func, query).into_bytes()
             }
+            SendCommand::FUNCTION(func, _) => format!("FUNCTION {}\r\n", func).into_bytes(),
             SendCommand::ACTS(command, input) => {
                 if let Some(input_num) = input {
                     format!("ACTS {} {}\r\n", command, input_num).into_bytes()
@@ -190,6 +247,7 @@ impl From<SendCommand> for Vec<u8> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SUBSCRIBECommand {
     TALLY,
     ACTS,
@@ -205,30 +263,44 @@ impl Display for SUBSCRIBECommand {
 }
 
 impl TryFrom<&mut TcpStream> for RecvCommand {
-    type Error = anyhow::Error;
+    type Error = VmixError;
 
     fn try_from(stream: &mut TcpStream) -> Result<Self, Self::Error> {
+        Self::read_from(stream, DEFAULT_XML_READ_TIMEOUT)
+    }
+}
+
+impl RecvCommand {
+    /// Read a single response or event from the stream
+    ///
+    /// `xml_read_timeout` bounds how long to wait for the body of an `XML` response.
+    pub fn read_from(stream: &mut TcpStream, xml_read_timeout: Duration) -> Result<Self> {
         // Read directly from TcpStream to avoid BufReader buffering issues
-        let mut value = String::new();
+        let mut line = Vec::new();
         let mut buffer = [0u8; 1];
 
         // Read byte by byte until we hit \n
         loop {
-            let bytes_read = stream.read(&mut buffer)?;
+            let bytes_read = match stream.read(&mut buffer) {
+                Ok(n) => n,
+                // Once a line has started, wait for the rest of it instead of dropping it
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock && !line.is_empty() => {
+                    std::thread::sleep(Duration::from_millis(1));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
             if bytes_read == 0 {
-                return Err(anyhow::anyhow!(std::io::Error::new(
-                    std::io::ErrorKind::ConnectionAborted,
-                    "connection aborted"
-                )));
+                return Err(VmixError::Disconnected);
             }
 
-            let ch = buffer[0] as char;
-            value.push(ch);
-
-            if ch == '\n' {
+            line.push(buffer[0]);
+            if buffer[0] == b'\n' {
                 break;
             }
         }
+        // Titles and input names may be non-ASCII
+        let value = String::from_utf8(line)?;
 
         // remove \r\n
         let value = value.lines().collect::<String>();
@@ -238,15 +310,30 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
         // first element
         let command = commands
             .first()
-            .ok_or_else(|| anyhow::anyhow!("Empty command"))?;
-        let status: Status = commands.get(1).unwrap().to_owned().into();
+            .ok_or_else(|| VmixError::Protocol("empty command".to_string()))?;
+        let status: Status = commands
+            .get(1)
+            .ok_or_else(|| VmixError::Protocol(format!("missing status: {:?}", value)))?
+            .to_owned()
+            .into();
         let body: Option<String> = commands.get(2).cloned();
+        // Everything after the status, for responses whose body may contain spaces
+        // Example Response: FUNCTION ER Input not found\r\n
+        let detail: Option<String> = value
+            .trim()
+            .splitn(3, ' ')
+            .nth(2)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
         match command.as_str() {
             // Example Response: TALLY OK 0121...\r\n
             "TALLY" => {
                 let mut tally_map = HashMap::new();
                 // check if status is ok
-                let chars: Vec<char> = body.unwrap().chars().collect::<Vec<char>>();
+                let chars: Vec<char> = body
+                    .ok_or_else(|| VmixError::Protocol("TALLY without body".to_string()))?
+                    .chars()
+                    .collect::<Vec<char>>();
                 for (i, char) in chars.iter().enumerate() {
                     let tally: TallyData = (*char).into();
                     let mut index = i as InputNumber;
@@ -260,7 +347,10 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
             }
             // Example Response: FUNCTION OK PreviewInput\r\n
             // Example Response: FUNCTION ER Error message\r\n
-            "FUNCTION" => Ok(Self::FUNCTION(FunctionResponse { status, body })),
+            "FUNCTION" => Ok(Self::FUNCTION(FunctionResponse {
+                status,
+                body: detail,
+            })),
             // Example Response: ACTS OK Input 1 1\r\n
             "ACTS" => {
                 // 2以降のベクターを使用する
@@ -279,16 +369,12 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
                     let mut xml_buffer = vec![0u8; *len as usize];
                     let mut bytes_read = 0;
                     let start_time = std::time::Instant::now();
-                    let read_timeout = Duration::from_secs(5); // 5 second timeout for XML reads
 
                     while bytes_read < xml_buffer.len() {
                         match stream.read(&mut xml_buffer[bytes_read..]) {
                             Ok(0) => {
                                 // EOF reached before reading all expected bytes
-                                return Err(anyhow::anyhow!(std::io::Error::new(
-                                    std::io::ErrorKind::ConnectionAborted,
-                                    "connection aborted"
-                                )));
+                                return Err(VmixError::Disconnected);
                             }
                             Ok(n) => {
                                 bytes_read += n;
@@ -296,8 +382,10 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
                             Err(e) => match e.kind() {
                                 std::io::ErrorKind::WouldBlock => {
                                     // Non-blocking read would block, check timeout
-                                    if start_time.elapsed() > read_timeout {
-                                        return Err(anyhow::anyhow!("XML read timeout"));
+                                    if start_time.elapsed() > xml_read_timeout {
+                                        return Err(VmixError::Timeout(
+                                            "reading XML response body".to_string(),
+                                        ));
                                     }
                                     std::thread::sleep(Duration::from_millis(1));
                                     continue;
@@ -305,9 +393,9 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
                                 std::io::ErrorKind::ConnectionAborted
                                 | std::io::ErrorKind::ConnectionReset
                                 | std::io::ErrorKind::UnexpectedEof => {
-                                    return Err(anyhow::anyhow!(e));
+                                    return Err(VmixError::Disconnected);
                                 }
-                                _ => return Err(anyhow::anyhow!(e)),
+                                _ => return Err(VmixError::Io(e)),
                             },
                         }
                     }
@@ -315,17 +403,31 @@ impl TryFrom<&mut TcpStream> for RecvCommand {
                     let xml = String::from_utf8(xml_buffer)?.trim_end().to_string();
                     return Ok(Self::XML(XMLResponse { status, body: xml }));
                 }
-                Err(anyhow::anyhow!("Failed to read XML"))
+                Err(VmixError::Protocol(
+                    "XML response without body length".to_string(),
+                ))
             }
-            "XMLTEXT" => Ok(Self::XMLTEXT(XMLTextResponse { status, body })),
-            "SUBSCRIBE" => Ok(Self::SUBSCRIBE(SubscribeResponse { status, body })),
-            "UNSUBSCRIBE" => Ok(Self::UNSUBSCRIBE(UnsubscribeResponse { status, body })),
+            "XMLTEXT" => Ok(Self::XMLTEXT(XMLTextResponse {
+                status,
+                body: detail,
+            })),
+            "SUBSCRIBE" => Ok(Self::SUBSCRIBE(SubscribeResponse {
+                status,
+                body: detail,
+            })),
+            "UNSUBSCRIBE" => Ok(Self::UNSUBSCRIBE(UnsubscribeResponse {
+                status,
+                body: detail,
+            })),
             "QUIT" => Ok(Self::QUIT), // No body
             "VERSION" => Ok(Self::VERSION(VersionResponse {
                 status,
                 version: body,
             })),
-            _ => Err(anyhow::anyhow!("No matching command found: {:?}", command)),
+            _ => Err(VmixError::Protocol(format!(
+                "no matching command found: {:?}",
+                command
+            ))),
         }
     }
 }
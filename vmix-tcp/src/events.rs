@@ -0,0 +1,323 @@
+use crate::commands::RecvCommand;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+    future::Future,
+    sync::{Arc, Condvar, Mutex, Weak},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// Type of an event received from vMix, used to filter subscriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Tally,
+    Function,
+    Acts,
+    Xml,
+    XmlText,
+    Subscribe,
+    Unsubscribe,
+    Quit,
+    Version,
+}
+
+impl RecvCommand {
+    /// The kind of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            RecvCommand::TALLY(_) => EventKind::Tally,
+            RecvCommand::FUNCTION(_) => EventKind::Function,
+            RecvCommand::ACTS(_) => EventKind::Acts,
+            RecvCommand::XML(_) => EventKind::Xml,
+            RecvCommand::XMLTEXT(_) => EventKind::XmlText,
+            RecvCommand::SUBSCRIBE(_) => EventKind::Subscribe,
+            RecvCommand::UNSUBSCRIBE(_) => EventKind::Unsubscribe,
+            RecvCommand::QUIT => EventKind::Quit,
+            RecvCommand::VERSION(_) => EventKind::Version,
+        }
+    }
+}
+
+/// Selects which events a subscription receives
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_tcp::events::{EventFilter, EventKind};
+///
+/// let filter = EventFilter::only(&[EventKind::Tally, EventKind::Acts]);
+/// let subscription = vmix.subscribe(filter, 256);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFilter {
+    kinds: Option<Vec<EventKind>>,
+}
+
+impl EventFilter {
+    /// Receive every event
+    pub fn all() -> Self {
+        Self { kinds: None }
+    }
+
+    /// Receive only the given kinds of events
+    pub fn only(kinds: &[EventKind]) -> Self {
+        Self {
+            kinds: Some(kinds.to_vec()),
+        }
+    }
+
+    /// Check whether an event passes this filter
+    pub fn matches(&self, event: &RecvCommand) -> bool {
+        match &self.kinds {
+            None => true,
+            Some(kinds) => kinds.contains(&event.kind()),
+        }
+    }
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Error returned when receiving from a [`Subscription`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    /// The subscriber fell behind and this many events were dropped from its buffer
+    Lagged(u64),
+    /// No event arrived within the timeout
+    Timeout,
+    /// The connection is closed and every buffered event has been received
+    Closed,
+}
+
+impl Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lagged(count) => write!(f, "subscriber lagged behind by {} events", count),
+            Self::Timeout => write!(f, "timed out waiting for event"),
+            Self::Closed => write!(f, "event stream closed"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+struct SubscriberState<T> {
+    queue: VecDeque<T>,
+    missed: u64,
+    closed: bool,
+    /// Tasks waiting in [`Subscription::recv_async`]
+    wakers: Vec<Waker>,
+}
+
+impl<T> SubscriberState<T> {
+    /// The next result for the subscriber, or `None` if it has to wait
+    fn take(&mut self) -> Option<Result<T, RecvError>> {
+        if self.missed > 0 {
+            let missed = self.missed;
+            self.missed = 0;
+            return Some(Err(RecvError::Lagged(missed)));
+        }
+        if let Some(event) = self.queue.pop_front() {
+            return Some(Ok(event));
+        }
+        self.closed.then_some(Err(RecvError::Closed))
+    }
+}
+
+struct Subscriber<T> {
+    state: Mutex<SubscriberState<T>>,
+    ready: Condvar,
+    capacity: usize,
+}
+
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+type SubscriberEntry<T> = (Weak<Subscriber<T>>, Filter<T>);
+
+/// Fans events out to any number of independent subscribers
+///
+/// Each subscriber owns a bounded buffer. Publishing never blocks: when a
+/// buffer is full the oldest event is dropped and the subscriber is told how
+/// many events it missed on its next receive.
+pub(crate) struct Broadcaster<T> {
+    state: Mutex<BroadcasterState<T>>,
+}
+
+struct BroadcasterState<T> {
+    subscribers: Vec<SubscriberEntry<T>>,
+    closed: bool,
+}
+
+impl<T: Clone> Broadcaster<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(BroadcasterState {
+                subscribers: Vec::new(),
+                closed: false,
+            }),
+        }
+    }
+
+    pub(crate) fn subscribe<F>(&self, capacity: usize, filter: F) -> Subscription<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        let subscriber = Arc::new(Subscriber {
+            state: Mutex::new(SubscriberState {
+                queue: VecDeque::new(),
+                missed: 0,
+                closed: false,
+                wakers: Vec::new(),
+            }),
+            ready: Condvar::new(),
+            capacity: capacity.max(1),
+        });
+        match self.state.lock() {
+            Ok(mut state) if !state.closed => {
+                state
+                    .subscribers
+                    .push((Arc::downgrade(&subscriber), Box::new(filter)));
+            }
+            _ => {
+                // Nothing will ever be published, hand out a subscription that is already closed
+                if let Ok(mut state) = subscriber.state.lock() {
+                    state.closed = true;
+                }
+            }
+        }
+        Subscription { inner: subscriber }
+    }
+
+    /// Deliver an event to every matching subscriber, dropping subscribers that went away
+    pub(crate) fn publish(&self, event: &T) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.subscribers.retain(|(subscriber, filter)| {
+            let Some(subscriber) = subscriber.upgrade() else {
+                return false;
+            };
+            if filter(event) {
+                let mut wakers = Vec::new();
+                if let Ok(mut state) = subscriber.state.lock() {
+                    if state.queue.len() >= subscriber.capacity {
+                        state.queue.pop_front();
+                        state.missed += 1;
+                    }
+                    state.queue.push_back(event.clone());
+                    wakers = std::mem::take(&mut state.wakers);
+                }
+                subscriber.ready.notify_all();
+                wakers.into_iter().for_each(Waker::wake);
+            }
+            true
+        });
+    }
+
+    /// Mark every subscription as closed once its buffer has been drained
+    pub(crate) fn close(&self) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.closed = true;
+        for (subscriber, _) in state.subscribers.drain(..) {
+            if let Some(subscriber) = subscriber.upgrade() {
+                let mut wakers = Vec::new();
+                if let Ok(mut state) = subscriber.state.lock() {
+                    state.closed = true;
+                    wakers = std::mem::take(&mut state.wakers);
+                }
+                subscriber.ready.notify_all();
+                wakers.into_iter().for_each(Waker::wake);
+            }
+        }
+    }
+}
+
+/// Receiving end of a broadcast subscription
+///
+/// Dropping the subscription unsubscribes it.
+pub struct Subscription<T> {
+    inner: Arc<Subscriber<T>>,
+}
+
+/// Subscription to events received from vMix
+pub type EventSubscription = Subscription<RecvCommand>;
+
+impl<T> Subscription<T> {
+    /// Wait for the next event, blocking until one arrives or the stream closes
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.recv_deadline(None)
+    }
+
+    /// Wait for the next event for at most `timeout`
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvError> {
+        self.recv_deadline(Some(Instant::now() + timeout))
+    }
+
+    /// Take the next buffered event without waiting
+    pub fn try_recv(&self) -> Result<T, RecvError> {
+        self.recv_deadline(Some(Instant::now()))
+    }
+
+    /// Wait for the next event without blocking the thread
+    ///
+    /// The returned future works on any async runtime: the task is woken
+    /// when an event is published or the stream closes.
+    pub fn recv_async(&self) -> impl Future<Output = Result<T, RecvError>> + '_ {
+        std::future::poll_fn(move |cx| self.poll_recv(cx))
+    }
+
+    /// Take the next event, or arrange for `cx` to be woken when there is one
+    pub fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let Ok(mut state) = self.inner.state.lock() else {
+            return Poll::Ready(Err(RecvError::Closed));
+        };
+        if let Some(result) = state.take() {
+            return Poll::Ready(result);
+        }
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+
+    /// Number of events currently buffered for this subscriber
+    pub fn len(&self) -> usize {
+        self.inner.state.lock().map(|s| s.queue.len()).unwrap_or(0)
+    }
+
+    /// Whether no events are currently buffered for this subscriber
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn recv_deadline(&self, deadline: Option<Instant>) -> Result<T, RecvError> {
+        let mut state = self.inner.state.lock().map_err(|_| RecvError::Closed)?;
+        loop {
+            if let Some(result) = state.take() {
+                return result;
+            }
+            state = match deadline {
+                None => self
+                    .inner
+                    .ready
+                    .wait(state)
+                    .map_err(|_| RecvError::Closed)?,
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(RecvError::Timeout);
+                    }
+                    self.inner
+                        .ready
+                        .wait_timeout(state, deadline - now)
+                        .map_err(|_| RecvError::Closed)?
+                        .0
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::vmix::VmixApi;
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// How often the worker checks for shutdown between heartbeats
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Settings of a [`HealthMonitor`]
+///
+/// # Example
+/// ```rust,ignore
+/// let config = HeartbeatConfig::new(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(1))
+///     .max_missed(2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    interval: Duration,
+    timeout: Duration,
+    max_missed: u32,
+    samples: usize,
+}
+
+impl HeartbeatConfig {
+    /// Send a heartbeat every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            timeout: Duration::from_secs(2),
+            max_missed: 3,
+            samples: 32,
+        }
+    }
+
+    /// How long to wait for the answer to a heartbeat
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of consecutive unanswered heartbeats after which vMix is considered gone
+    pub fn max_missed(mut self, max_missed: u32) -> Self {
+        self.max_missed = max_missed.max(1);
+        self
+    }
+
+    /// Number of recent round trips the latency statistics are computed over
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+/// Round-trip times of recent heartbeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Number of round trips the statistics cover
+    pub samples: usize,
+    pub last: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl LatencyStats {
+    fn from_samples(samples: &VecDeque<Duration>) -> Option<Self> {
+        let last = *samples.back()?;
+        let total: Duration = samples.iter().sum();
+        Some(Self {
+            samples: samples.len(),
+            last,
+            min: samples.iter().copied().min()?,
+            max: samples.iter().copied().max()?,
+            mean: total / samples.len() as u32,
+        })
+    }
+}
+
+/// Health of the connection as seen by a [`HealthMonitor`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Health {
+    /// Whether the connection is open and vMix answers heartbeats
+    pub alive: bool,
+    /// When the last message of any kind was received from vMix
+    pub last_seen: Option<Instant>,
+    /// When the last heartbeat was answered
+    pub last_heartbeat: Option<Instant>,
+    /// Consecutive heartbeats that were not answered
+    pub missed: u32,
+    /// `None` until the first heartbeat is answered
+    pub latency: Option<LatencyStats>,
+}
+
+#[derive(Debug, Default)]
+struct Status {
+    last_heartbeat: Option<Instant>,
+    missed: u32,
+    latencies: VecDeque<Duration>,
+}
+
+/// Periodic heartbeat detecting a vMix that stopped answering
+///
+/// [`VmixApi::is_connected`] only notices connections that were closed or
+/// reset. The monitor sends a `VERSION` command every interval and measures
+/// the round trip, so a half-open connection or a hung vMix is reported as
+/// dead once `max_missed` heartbeats in a row go unanswered.
+///
+/// # Example
+/// ```rust,ignore
+/// use std::{sync::Arc, time::Duration};
+/// use vmix_tcp::{HealthMonitor, HeartbeatConfig, VmixApi};
+///
+/// let api = Arc::new(VmixApi::new(addr, Duration::from_secs(5))?);
+/// let health = HealthMonitor::start(api, HeartbeatConfig::new(Duration::from_secs(5)));
+/// if let Some(latency) = health.latency() {
+///     println!("alive: {}, mean latency: {:?}", health.is_alive(), latency.mean);
+/// }
+/// ```
+pub struct HealthMonitor {
+    api: Arc<VmixApi>,
+    status: Arc<Mutex<Status>>,
+    max_missed: u32,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Drop for HealthMonitor {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(worker) = self.worker.take()
+            && worker.join().is_err()
+        {
+            log_warn!("health monitor worker panicked");
+        }
+    }
+}
+
+impl HealthMonitor {
+    /// Start sending heartbeats, the first one immediately
+    pub fn start(api: Arc<VmixApi>, config: HeartbeatConfig) -> Self {
+        let status = Arc::new(Mutex::new(Status::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let worker = Worker {
+            api: api.clone(),
+            status: status.clone(),
+            shutdown: shutdown.clone(),
+            config: config.clone(),
+        };
+        let worker = std::thread::spawn(move || worker.run());
+
+        Self {
+            api,
+            status,
+            max_missed: config.max_missed,
+            shutdown,
+            worker: Some(worker),
+        }
+    }
+
+    /// Whether the connection is open and fewer than `max_missed` heartbeats in a row went unanswered
+    pub fn is_alive(&self) -> bool {
+        self.health().alive
+    }
+
+    /// Latency statistics over the recent heartbeats
+    pub fn latency(&self) -> Option<LatencyStats> {
+        self.health().latency
+    }
+
+    /// A snapshot of the connection health
+    pub fn health(&self) -> Health {
+        let status = match self.status.lock() {
+            Ok(status) => status,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        Health {
+            alive: self.api.is_connected() && status.missed < self.max_missed,
+            last_seen: self.api.last_received(),
+            last_heartbeat: status.last_heartbeat,
+            missed: status.missed,
+            latency: LatencyStats::from_samples(&status.latencies),
+        }
+    }
+
+    /// Whether heartbeats are still being sent
+    pub fn is_running(&self) -> bool {
+        self.worker.as_ref().is_some_and(|w| !w.is_finished())
+    }
+}
+
+struct Worker {
+    api: Arc<VmixApi>,
+    status: Arc<Mutex<Status>>,
+    shutdown: Arc<AtomicBool>,
+    config: HeartbeatConfig,
+}
+
+impl Worker {
+    fn run(self) {
+        let mut next_heartbeat = Instant::now();
+
+        while !self.shutdown.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if now < next_heartbeat {
+                std::thread::sleep((next_heartbeat - now).min(POLL_INTERVAL));
+                continue;
+            }
+            next_heartbeat = now + self.config.interval;
+
+            let sent_at = Instant::now();
+            let result = self.api.version(self.config.timeout);
+            let Ok(mut status) = self.status.lock() else {
+                break;
+            };
+            match result {
+                Ok(_) => {
+                    let latency = sent_at.elapsed();
+                    log_trace!(latency_ms = latency.as_secs_f64() * 1000.0, "heartbeat");
+                    status.last_heartbeat = Some(Instant::now());
+                    status.missed = 0;
+                    if status.latencies.len() == self.config.samples {
+                        status.latencies.pop_front();
+                    }
+                    status.latencies.push_back(latency);
+                }
+                Err(_err) => {
+                    status.missed = status.missed.saturating_add(1);
+                    log_debug!(error = %_err, missed = status.missed, "heartbeat failed");
+                }
+            }
+        }
+    }
+}
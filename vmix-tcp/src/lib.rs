@@ -1,15 +1,44 @@
+#[macro_use]
+mod log;
+
+pub mod activator_state;
 pub mod acts;
+#[cfg(feature = "async")]
+pub mod async_api;
+pub mod builder;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod commands;
+pub mod events;
+pub mod health;
+pub mod live_state;
+mod pending;
+pub mod queue;
+pub mod tally;
 pub mod traits;
 pub mod vmix;
 
 // Re-export commonly used types
-pub use acts::ActivatorsData;
+pub use activator_state::ActivatorState;
+pub use acts::{ActivatorsData, Bus};
+#[cfg(feature = "async")]
+pub use async_api::AsyncVmixApi;
+pub use builder::VmixApiBuilder;
+#[cfg(feature = "client")]
+pub use client::{ClientCapabilities, Transport, VmixClient};
 pub use commands::{
     InputNumber, RecvCommand, SUBSCRIBECommand, SendCommand, TallyData, TallyResponse,
 };
+pub use events::{EventFilter, EventKind, EventSubscription, RecvError, Subscription};
+pub use health::{Health, HealthMonitor, HeartbeatConfig, LatencyStats};
+pub use live_state::LiveState;
+pub use queue::CommandPriority;
+pub use tally::TallyTracker;
+#[cfg(feature = "async")]
+pub use traits::AsyncVmixTcpApiClient;
 pub use traits::VmixTcpApiClient;
 pub use vmix::VmixApi;
 
 // Re-export vmix-core for convenience
 pub use vmix_core;
+pub use vmix_core::{Capabilities, FunctionParams, VmixError, VmixFunction, VmixVersion};
@@ -0,0 +1,501 @@
+use crate::{
+    acts::{ActivatorsData, Bus},
+    commands::{InputNumber, RecvCommand, SUBSCRIBECommand, SendCommand, TallyData},
+    events::{Broadcaster, EventFilter, EventKind, RecvError, Subscription},
+    vmix::VmixApi,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use vmix_core::{
+    AudioBus, Input, Mix, State, StateChange, Vmix,
+    error::{Result, VmixError},
+};
+
+/// Time allowed for the initial XML snapshot to arrive
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Number of events buffered between the reader thread and the live state worker
+const EVENT_BUFFER: usize = 1024;
+/// How often the worker checks for shutdown while no events arrive
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Delay before fetching a snapshot again after one could not be parsed,
+/// doubled after every further failure
+const RESYNC_BACKOFF: Duration = Duration::from_millis(250);
+/// Longest delay between snapshots that cannot be parsed
+const MAX_RESYNC_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A [`Vmix`] model kept current from TCP events
+///
+/// `LiveState` fetches an XML snapshot, then applies `TALLY` and `ACTS` events
+/// to it in place. Some changes (inputs added or renamed, transitions, ...)
+/// are not reported by events, so the snapshot is re-fetched every
+/// `resync_interval` and whenever an event cannot be applied. Every
+/// difference is published as a [`StateChange`].
+///
+/// One snapshot is requested at a time. After a snapshot that cannot be
+/// parsed, the next one is requested after a delay that doubles with every
+/// further failure, up to 30 seconds.
+///
+/// Snapshots are requested with a plain `XML` command and read from the event
+/// stream, so they are ordered with respect to the events around them.
+///
+/// # Example
+/// ```rust,ignore
+/// use std::{sync::Arc, time::Duration};
+/// use vmix_tcp::{LiveState, VmixApi};
+///
+/// let api = Arc::new(VmixApi::new(addr, Duration::from_secs(5))?);
+/// let live = LiveState::start(api, Duration::from_secs(30))?;
+/// let changes = live.subscribe(256);
+/// while let Ok(change) = changes.recv() {
+///     println!("{:?}, active input is now {}", change, live.read(|vmix| vmix.active.clone()));
+/// }
+/// ```
+pub struct LiveState {
+    state: Arc<RwLock<Arc<Vmix>>>,
+    changes: Arc<Broadcaster<StateChange>>,
+    resync_requested: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Drop for LiveState {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(worker) = self.worker.take()
+            && worker.join().is_err()
+        {
+            log_warn!("live state worker panicked");
+        }
+    }
+}
+
+impl LiveState {
+    /// Fetch the initial snapshot and start following events
+    ///
+    /// Sends `SUBSCRIBE TALLY` and `SUBSCRIBE ACTS`, and returns once the
+    /// first snapshot has been received.
+    pub fn start(api: Arc<VmixApi>, resync_interval: Duration) -> Result<Self> {
+        let events = api.subscribe(
+            EventFilter::only(&[EventKind::Tally, EventKind::Acts, EventKind::Xml]),
+            EVENT_BUFFER,
+        );
+        api.send_command(SendCommand::SUBSCRIBE(SUBSCRIBECommand::TALLY))?;
+        api.send_command(SendCommand::SUBSCRIBE(SUBSCRIBECommand::ACTS))?;
+        api.send_command(SendCommand::XML)?;
+
+        // Events received before the snapshot are already part of it
+        let deadline = Instant::now() + SNAPSHOT_TIMEOUT;
+        let vmix = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match events.recv_timeout(remaining) {
+                Ok(RecvCommand::XML(response)) => break vmix_core::from_str(&response.body)?,
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Timeout) => {
+                    return Err(VmixError::Timeout("waiting for XML snapshot".to_string()));
+                }
+                Err(RecvError::Closed) => return Err(VmixError::Disconnected),
+            }
+        };
+
+        let state = Arc::new(RwLock::new(Arc::new(vmix)));
+        let changes = Arc::new(Broadcaster::new());
+        let resync_requested = Arc::new(AtomicBool::new(false));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let worker = Worker {
+            api,
+            events,
+            state: state.clone(),
+            changes: changes.clone(),
+            resync_requested: resync_requested.clone(),
+            shutdown: shutdown.clone(),
+            resync_interval,
+        };
+        let worker = std::thread::spawn(move || worker.run());
+
+        Ok(Self {
+            state,
+            changes,
+            resync_requested,
+            shutdown,
+            worker: Some(worker),
+        })
+    }
+
+    /// A copy of the current state
+    pub fn snapshot(&self) -> Vmix {
+        self.read(Vmix::clone)
+    }
+
+    /// Read the current state without copying it
+    pub fn read<R>(&self, f: impl FnOnce(&Vmix) -> R) -> R {
+        match self.state.read() {
+            Ok(state) => f(&state),
+            Err(poisoned) => f(&poisoned.into_inner()),
+        }
+    }
+
+    /// Subscribe to changes of the state
+    ///
+    /// Up to `capacity` changes are buffered, see [`Subscription`].
+    pub fn subscribe(&self, capacity: usize) -> Subscription<StateChange> {
+        self.changes.subscribe(capacity, |_| true)
+    }
+
+    /// Fetch a fresh snapshot as soon as possible instead of waiting for the next resync
+    pub fn resync(&self) {
+        self.resync_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether events are still being followed
+    pub fn is_running(&self) -> bool {
+        self.worker.as_ref().is_some_and(|w| !w.is_finished())
+    }
+}
+
+struct Worker {
+    api: Arc<VmixApi>,
+    events: Subscription<RecvCommand>,
+    state: Arc<RwLock<Arc<Vmix>>>,
+    changes: Arc<Broadcaster<StateChange>>,
+    resync_requested: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    resync_interval: Duration,
+}
+
+impl Worker {
+    fn run(self) {
+        let mut next_resync = Instant::now() + self.resync_interval;
+        // Only one snapshot is requested at a time, until it arrives or times out
+        let mut in_flight: Option<Instant> = None;
+        let mut failures: u32 = 0;
+        let mut retry_at = Instant::now();
+
+        while !self.shutdown.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if in_flight.is_some_and(|sent| now.duration_since(sent) >= SNAPSHOT_TIMEOUT) {
+                log_debug!("XML snapshot not received, requesting another");
+                in_flight = None;
+                self.resync_requested.store(true, Ordering::SeqCst);
+            }
+            if in_flight.is_none()
+                && now >= retry_at
+                && (self.resync_requested.swap(false, Ordering::SeqCst) || now >= next_resync)
+            {
+                if self.api.send_command(SendCommand::XML).is_err() {
+                    break;
+                }
+                in_flight = Some(now);
+                next_resync = now + self.resync_interval;
+            }
+
+            let wait = next_resync
+                .saturating_duration_since(Instant::now())
+                .min(POLL_INTERVAL);
+            match self.events.recv_timeout(wait) {
+                Ok(event) => {
+                    let snapshot = matches!(event, RecvCommand::XML(_));
+                    let applied = self.handle(event);
+                    if snapshot {
+                        in_flight = None;
+                        if applied {
+                            failures = 0;
+                        } else {
+                            failures = failures.saturating_add(1);
+                            retry_at = Instant::now() + resync_backoff(failures);
+                        }
+                    }
+                    if !applied {
+                        self.resync_requested.store(true, Ordering::SeqCst);
+                    }
+                }
+                // Deltas were lost, only a new snapshot can tell what changed
+                Err(RecvError::Lagged(_)) => self.resync_requested.store(true, Ordering::SeqCst),
+                Err(RecvError::Timeout) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        self.changes.close();
+    }
+
+    /// Apply an event to a copy of the state, then swap the copy in
+    ///
+    /// The worker is the only writer, so the copy is made, changed and compared
+    /// with the current state without holding the lock, which readers only
+    /// lose while the new state is swapped in. Returns `false` if the event
+    /// could not be applied and a new snapshot is needed.
+    fn handle(&self, event: RecvCommand) -> bool {
+        let Ok(before) = self.state.read().map(|state| Arc::clone(&state)) else {
+            return true;
+        };
+        let mut after = None;
+        let applied = match &event {
+            RecvCommand::XML(response) => match vmix_core::from_str(&response.body) {
+                Ok(snapshot) => {
+                    after = Some(snapshot);
+                    true
+                }
+                Err(_err) => {
+                    log_warn!(error = %_err, "failed to parse XML snapshot");
+                    false
+                }
+            },
+            RecvCommand::TALLY(response) => {
+                let state = after.insert(Vmix::clone(&before));
+                apply_tally(state, &response.body)
+            }
+            RecvCommand::ACTS(response) => {
+                let state = after.insert(Vmix::clone(&before));
+                apply_activator(state, &response.body)
+            }
+            _ => true,
+        };
+
+        if !applied {
+            log_debug!(kind = ?event.kind(), "event does not match the snapshot, resyncing");
+        }
+        let Some(after) = after else {
+            return applied;
+        };
+        let changes = vmix_core::diff(&before, &after);
+        if let Ok(mut state) = self.state.write() {
+            *state = Arc::new(after);
+        }
+        for change in &changes {
+            self.changes.publish(change);
+        }
+        applied
+    }
+}
+
+/// Delay before the next snapshot after `failures` consecutive ones could not be parsed
+fn resync_backoff(failures: u32) -> Duration {
+    RESYNC_BACKOFF
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_RESYNC_BACKOFF)
+}
+
+fn find_input(vmix: &mut Vmix, number: InputNumber) -> Option<&mut Input> {
+    let number = number.to_string();
+    vmix.inputs.input.iter_mut().find(|i| i.number == number)
+}
+
+fn audio_bus(vmix: &mut Vmix, bus: Bus) -> Option<&mut AudioBus> {
+    let audio = &mut vmix.audio;
+    match bus {
+        Bus::A => audio.bus_a.as_mut(),
+        Bus::B => audio.bus_b.as_mut(),
+        Bus::C => audio.bus_c.as_mut(),
+        Bus::D => audio.bus_d.as_mut(),
+        Bus::E => audio.bus_e.as_mut(),
+        Bus::F => audio.bus_f.as_mut(),
+        Bus::G => audio.bus_g.as_mut(),
+    }
+}
+
+/// Add or remove `bus` from an `audiobusses` list like `M,A,C`
+fn set_audio_bus(busses: &mut Option<String>, bus: &str, routed: bool) {
+    let mut list: Vec<String> = busses
+        .iter()
+        .flat_map(|b| b.split(','))
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty() && b != bus)
+        .collect();
+    if routed {
+        list.push(bus.to_string());
+    }
+    // vMix lists the master bus first, then A to G
+    list.sort_by_key(|b| if b == "M" { String::new() } else { b.clone() });
+    *busses = Some(list.join(","));
+}
+
+/// Set the live or preview input of `mix`
+fn set_mix_input(vmix: &mut Vmix, mix: u8, input: InputNumber, preview: bool) {
+    let input = input.to_string();
+    if mix == 1 {
+        if preview {
+            vmix.preview = input;
+        } else {
+            vmix.active = input;
+        }
+        return;
+    }
+
+    let number = mix.to_string();
+    let index = match vmix.mix.iter().position(|m| m.number == number) {
+        Some(index) => index,
+        None => {
+            vmix.mix.push(Mix {
+                number,
+                preview: String::new(),
+                active: String::new(),
+            });
+            vmix.mix.len() - 1
+        }
+    };
+    if preview {
+        vmix.mix[index].preview = input;
+    } else {
+        vmix.mix[index].active = input;
+    }
+}
+
+/// Apply a TALLY event to the main mix, returning `false` if it does not match the snapshot
+///
+/// TALLY also lights inputs shown on overlays, so the live and preview inputs
+/// only change when the current one is no longer lit.
+fn apply_tally(vmix: &mut Vmix, tally: &HashMap<InputNumber, TallyData>) -> bool {
+    if tally.len() != vmix.inputs.input.len() {
+        return false;
+    }
+
+    let on_overlay: Vec<String> = vmix
+        .overlays
+        .overlay
+        .iter()
+        .filter_map(|o| o.input.clone())
+        .collect();
+    let lit = |light: TallyData| {
+        let mut inputs: Vec<InputNumber> = tally
+            .iter()
+            .filter(|(input, data)| **data == light && !on_overlay.contains(&input.to_string()))
+            .map(|(input, _)| *input)
+            .collect();
+        inputs.sort_unstable();
+        inputs
+    };
+    let is_lit = |current: &str, light: TallyData| {
+        current
+            .parse::<InputNumber>()
+            .is_ok_and(|input| tally.get(&input) == Some(&light))
+    };
+
+    if !is_lit(&vmix.active, TallyData::PROGRAM)
+        && let Some(input) = lit(TallyData::PROGRAM).first()
+    {
+        vmix.active = input.to_string();
+    }
+    if !is_lit(&vmix.preview, TallyData::PREVIEW)
+        && let Some(input) = lit(TallyData::PREVIEW).first()
+    {
+        vmix.preview = input.to_string();
+    }
+    true
+}
+
+/// Apply an activator, returning `false` if it refers to something missing from the snapshot
+fn apply_activator(vmix: &mut Vmix, data: &ActivatorsData) -> bool {
+    match data {
+        // The input going off is always followed by the one going on
+        ActivatorsData::InputMix { active: false, .. }
+        | ActivatorsData::InputPreviewMix { active: false, .. } => {}
+        ActivatorsData::InputMix { mix, input, .. } => set_mix_input(vmix, *mix, *input, false),
+        ActivatorsData::InputPreviewMix { mix, input, .. } => {
+            set_mix_input(vmix, *mix, *input, true)
+        }
+        ActivatorsData::InputPlaying(input, playing) => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            input.state = if *playing {
+                State::Running
+            } else {
+                State::Paused
+            };
+        }
+        ActivatorsData::InputVolume(input, level) => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            input.volume = Some(f64::from(*level) * 100.0);
+        }
+        ActivatorsData::InputAudio(input, on) => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            input.muted = Some(!on);
+        }
+        ActivatorsData::InputSolo(input, solo) => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            input.solo = Some(*solo);
+        }
+        ActivatorsData::InputBusAudio { input, bus, active } => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            set_audio_bus(&mut input.audiobusses, &bus.to_string(), *active);
+        }
+        ActivatorsData::InputMasterAudio(input, active) => {
+            let Some(input) = find_input(vmix, *input) else {
+                return false;
+            };
+            set_audio_bus(&mut input.audiobusses, "M", *active);
+        }
+        ActivatorsData::MasterVolume(level) => {
+            vmix.audio.master.volume = f64::from(*level) * 100.0;
+        }
+        ActivatorsData::MasterHeadphones(level) => {
+            vmix.audio.master.headphones_volume = Some(f64::from(*level) * 100.0);
+        }
+        ActivatorsData::MasterAudio(on) => vmix.audio.master.muted = !on,
+        ActivatorsData::BusVolume { bus, level } => {
+            let Some(bus) = audio_bus(vmix, *bus) else {
+                return false;
+            };
+            bus.volume = f64::from(*level) * 100.0;
+        }
+        ActivatorsData::BusAudio { bus, active } => {
+            let Some(bus) = audio_bus(vmix, *bus) else {
+                return false;
+            };
+            bus.muted = !active;
+        }
+        ActivatorsData::BusSolo { bus, active } => {
+            let Some(bus) = audio_bus(vmix, *bus) else {
+                return false;
+            };
+            bus.solo = Some(*active);
+        }
+        ActivatorsData::FadeToBlack(active) => vmix.fade_to_black = *active,
+        ActivatorsData::Recording(active) => vmix.recording = *active,
+        ActivatorsData::Streaming(active) => vmix.streaming = *active,
+        ActivatorsData::External(active) => vmix.external = *active,
+        ActivatorsData::Fullscreen(active) => vmix.fullscreen = *active,
+        ActivatorsData::Overlay {
+            overlay,
+            input,
+            active,
+        } => {
+            let number = overlay.to_string();
+            let Some(overlay) = vmix
+                .overlays
+                .overlay
+                .iter_mut()
+                .find(|o| o.number == number)
+            else {
+                return false;
+            };
+            let input = input.to_string();
+            if *active {
+                overlay.input = Some(input);
+            } else if overlay.input.as_deref() == Some(input.as_str()) {
+                overlay.input = None;
+            }
+        }
+        // Not part of the XML model
+        ActivatorsData::InputHeadphones(_, _)
+        | ActivatorsData::ReplayPlaying(_)
+        | ActivatorsData::Unknown(_) => {}
+    }
+    true
+}
@@ -0,0 +1,25 @@
+// Internal logging macros
+//
+// With the `tracing` feature these forward to the matching `tracing` macro,
+// otherwise they expand to nothing so the library never writes to stderr.
+
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)*);
+    };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+    };
+}
@@ -0,0 +1,83 @@
+use crate::commands::{RecvCommand, SendCommand};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::mpsc::{SyncSender, TrySendError},
+};
+
+/// Command types whose responses are matched back to the request that caused them
+///
+/// vMix answers these commands in the order they were sent, so responses of
+/// the same kind can be paired with pending requests first-in, first-out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ResponseKind {
+    Function,
+    XmlText,
+    Xml,
+    Version,
+}
+
+impl ResponseKind {
+    pub(crate) fn of_request(command: &SendCommand) -> Option<Self> {
+        match command {
+            SendCommand::FUNCTION(_, _) => Some(Self::Function),
+            SendCommand::XMLTEXT(_) => Some(Self::XmlText),
+            SendCommand::XML => Some(Self::Xml),
+            SendCommand::VERSION => Some(Self::Version),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn of_response(command: &RecvCommand) -> Option<Self> {
+        match command {
+            RecvCommand::FUNCTION(_) => Some(Self::Function),
+            RecvCommand::XMLTEXT(_) => Some(Self::XmlText),
+            RecvCommand::XML(_) => Some(Self::Xml),
+            RecvCommand::VERSION(_) => Some(Self::Version),
+            _ => None,
+        }
+    }
+}
+
+/// Requests waiting for their response, queued per command type
+#[derive(Default)]
+pub(crate) struct PendingRequests {
+    queues: HashMap<ResponseKind, VecDeque<Option<SyncSender<RecvCommand>>>>,
+}
+
+impl PendingRequests {
+    /// Queue a command about to be written, its response will be delivered on `reply`
+    ///
+    /// Commands nobody waits for are registered with `None` so their responses
+    /// keep their place in line and go to the event stream.
+    pub(crate) fn register(&mut self, kind: ResponseKind, reply: Option<SyncSender<RecvCommand>>) {
+        self.queues.entry(kind).or_default().push_back(reply);
+    }
+
+    /// Hand a response to the oldest request of the same kind
+    ///
+    /// Returns the response back when it is an unsolicited event or the response
+    /// to a command nobody waits for. The late response of a request that already
+    /// timed out is dropped: it still consumes its response so later responses stay
+    /// correctly paired, but it is not an event.
+    pub(crate) fn resolve(&mut self, response: RecvCommand) -> Option<RecvCommand> {
+        let Some(kind) = ResponseKind::of_response(&response) else {
+            return Some(response);
+        };
+        let Some(Some(waiter)) = self.queues.get_mut(&kind).and_then(|q| q.pop_front()) else {
+            return Some(response);
+        };
+        match waiter.try_send(response) {
+            Ok(()) => None,
+            Err(TrySendError::Disconnected(_)) => {
+                log_debug!(?kind, "dropped the response of a timed out request");
+                None
+            }
+            Err(TrySendError::Full(response)) => Some(response),
+        }
+    }
+
+    /// Drop every pending request so waiters fail immediately instead of timing out
+    pub(crate) fn clear(&mut self) {
+        self.queues.clear();
+    }
+}
@@ -0,0 +1,355 @@
+use crate::commands::{RecvCommand, SendCommand};
+use std::{
+    collections::HashMap,
+    sync::{
+        Condvar, Mutex,
+        mpsc::{Receiver, SyncSender},
+    },
+    time::{Duration, Instant},
+};
+use vmix_core::{
+    error::{Result, VmixError},
+    query::FunctionParams,
+};
+
+/// Functions that switch what is on air, sent first with `prioritize_switching`
+const HIGH_PRIORITY: &[&str] = &[
+    "Cut",
+    "Fade",
+    "CutDirect",
+    "FadeToBlack",
+    "QuickPlay",
+    "ActiveInput",
+    "Zoom",
+    "Wipe",
+    "Slide",
+    "Fly",
+    "CrossZoom",
+    "FlyRotate",
+    "Cube",
+    "CubeZoom",
+    "VerticalWipe",
+    "VerticalSlide",
+    "Merge",
+    "WipeReverse",
+    "SlideReverse",
+    "VerticalWipeReverse",
+    "VerticalSlideReverse",
+];
+/// Prefixes of numbered switching functions, e.g. `Transition1` or `OverlayInput2In`
+const HIGH_PRIORITY_PREFIXES: &[&str] = &["Transition", "Stinger", "OverlayInput"];
+
+/// Continuous controls (faders, pan, zoom) where only the latest value matters,
+/// sent last with `prioritize_switching`
+const COALESCED: &[&str] = &[
+    "SetVolume",
+    "SetVolumeFade",
+    "SetBalance",
+    "SetGain",
+    "SetMasterVolume",
+    "SetHeadphonesVolume",
+    "SetBusAVolume",
+    "SetBusBVolume",
+    "SetBusCVolume",
+    "SetBusDVolume",
+    "SetBusEVolume",
+    "SetBusFVolume",
+    "SetBusGVolume",
+    "SetPanX",
+    "SetPanY",
+    "SetZoom",
+];
+
+/// Order in which queued commands are written to vMix
+///
+/// Commands of a higher priority are sent first, ahead of commands queued
+/// earlier. Commands of the same priority are sent in the order they were
+/// queued. Every function is `Normal` unless configured otherwise with
+/// [`VmixApiBuilder::function_priority`](crate::VmixApiBuilder::function_priority)
+/// or [`VmixApiBuilder::prioritize_switching`](crate::VmixApiBuilder::prioritize_switching).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommandPriority {
+    /// `QUIT`, and continuous controls such as `SetVolume` with `prioritize_switching`
+    Low,
+    /// Every other command, in the order they were queued
+    Normal,
+    /// Switching functions such as `Cut`, `Fade` or `OverlayInput1In` with `prioritize_switching`
+    High,
+}
+
+/// How the writer thread orders, merges and paces outgoing commands
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QueuePolicy {
+    pub(crate) priorities: HashMap<String, CommandPriority>,
+    pub(crate) prioritize_switching: bool,
+    pub(crate) coalesce: HashMap<String, bool>,
+    pub(crate) rate_limit: Option<u32>,
+}
+
+impl QueuePolicy {
+    fn priority(&self, command: &SendCommand) -> CommandPriority {
+        match command {
+            SendCommand::FUNCTION(name, _) => {
+                if let Some(priority) = self.priorities.get(name) {
+                    *priority
+                } else if !self.prioritize_switching {
+                    CommandPriority::Normal
+                } else if HIGH_PRIORITY.contains(&name.as_str())
+                    || HIGH_PRIORITY_PREFIXES.iter().any(|p| name.starts_with(p))
+                {
+                    CommandPriority::High
+                } else if COALESCED.contains(&name.as_str()) {
+                    CommandPriority::Low
+                } else {
+                    CommandPriority::Normal
+                }
+            }
+            // Everything queued before it still goes out
+            SendCommand::QUIT => CommandPriority::Low,
+            _ => CommandPriority::Normal,
+        }
+    }
+
+    /// Function name and input a queued command may be replaced by a newer one for
+    fn coalesce_key(&self, command: &SendCommand) -> Option<(String, Option<String>)> {
+        let SendCommand::FUNCTION(name, query) = command else {
+            return None;
+        };
+        let coalesce = self
+            .coalesce
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| COALESCED.contains(&name.as_str()));
+        if !coalesce {
+            return None;
+        }
+        let input = query
+            .as_deref()
+            .and_then(FunctionParams::parse)
+            .and_then(|params| params.get("Input").map(str::to_string));
+        Some((name.clone(), input))
+    }
+}
+
+/// A command waiting to be written, with the channel its response goes to
+pub(crate) struct Outgoing {
+    pub(crate) command: SendCommand,
+    pub(crate) reply: Option<SyncSender<RecvCommand>>,
+}
+
+struct Entry {
+    priority: CommandPriority,
+    sequence: u64,
+    coalesce_key: Option<(String, Option<String>)>,
+    outgoing: Outgoing,
+}
+
+#[derive(Default)]
+struct Queue {
+    entries: Vec<Entry>,
+    next_sequence: u64,
+    closed: bool,
+}
+
+/// Token bucket allowing `per_second` commands per second, in bursts of up to `per_second`
+struct RateLimiter {
+    per_second: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(per_second: u32) -> Self {
+        let per_second = f64::from(per_second.max(1));
+        Self {
+            per_second,
+            tokens: per_second,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    fn try_acquire(&mut self) -> std::result::Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.per_second);
+        self.refilled_at = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.per_second,
+            ))
+        }
+    }
+}
+
+/// Outgoing commands shared by the API handle and the writer thread
+///
+/// Holds up to `capacity` commands; queueing more waits for the writer thread
+/// to take some.
+pub(crate) struct CommandQueue {
+    policy: QueuePolicy,
+    capacity: usize,
+    queue: Mutex<Queue>,
+    ready: Condvar,
+    space: Condvar,
+    limiter: Mutex<Option<RateLimiter>>,
+}
+
+impl CommandQueue {
+    pub(crate) fn new(policy: QueuePolicy, capacity: usize) -> Self {
+        let limiter = policy.rate_limit.map(RateLimiter::new);
+        Self {
+            policy,
+            capacity: capacity.max(1),
+            queue: Mutex::new(Queue::default()),
+            ready: Condvar::new(),
+            space: Condvar::new(),
+            limiter: Mutex::new(limiter),
+        }
+    }
+
+    /// Queue a command, waiting until `deadline` (or indefinitely with `None`) while the queue is full
+    ///
+    /// A queued command for the same control is replaced if the command can be
+    /// coalesced, which never waits. Commands whose response is awaited are
+    /// never coalesced.
+    pub(crate) fn push(&self, outgoing: Outgoing, deadline: Option<Instant>) -> Result<()> {
+        let mut queue = self.queue.lock().map_err(|_| VmixError::Disconnected)?;
+        loop {
+            if queue.closed {
+                return Err(VmixError::Disconnected);
+            }
+            if queue.entries.len() < self.capacity || self.coalesces(&queue, &outgoing) {
+                self.insert(&mut queue, outgoing);
+                return Ok(());
+            }
+            queue = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(VmixError::Timeout(
+                            "waiting for room in the queue".to_string(),
+                        ));
+                    }
+                    self.space
+                        .wait_timeout(queue, remaining)
+                        .map_err(|_| VmixError::Disconnected)?
+                        .0
+                }
+                None => self
+                    .space
+                    .wait(queue)
+                    .map_err(|_| VmixError::Disconnected)?,
+            };
+        }
+    }
+
+    /// Move commands from `receiver` to the queue while it has room, without waiting
+    pub(crate) fn push_from(&self, receiver: &Receiver<SendCommand>) {
+        let Ok(mut queue) = self.queue.lock() else {
+            return;
+        };
+        while !queue.closed && queue.entries.len() < self.capacity {
+            let Ok(command) = receiver.try_recv() else {
+                break;
+            };
+            self.insert(
+                &mut queue,
+                Outgoing {
+                    command,
+                    reply: None,
+                },
+            );
+        }
+    }
+
+    fn coalesce_key(&self, outgoing: &Outgoing) -> Option<(String, Option<String>)> {
+        match outgoing.reply {
+            Some(_) => None,
+            None => self.policy.coalesce_key(&outgoing.command),
+        }
+    }
+
+    /// Whether `outgoing` would replace a queued command
+    fn coalesces(&self, queue: &Queue, outgoing: &Outgoing) -> bool {
+        self.coalesce_key(outgoing).is_some_and(|key| {
+            queue
+                .entries
+                .iter()
+                .any(|e| e.coalesce_key.as_ref() == Some(&key))
+        })
+    }
+
+    /// Add a command behind every queued one, dropping a queued command it replaces
+    ///
+    /// The replacement is not written in place of the command it replaces, so
+    /// it still goes out after every command queued before it.
+    fn insert(&self, queue: &mut Queue, outgoing: Outgoing) {
+        let coalesce_key = self.coalesce_key(&outgoing);
+        if let Some(key) = &coalesce_key
+            && let Some(index) = queue
+                .entries
+                .iter()
+                .position(|e| e.coalesce_key.as_ref() == Some(key))
+        {
+            log_trace!(function = %key.0, "coalesced queued command");
+            queue.entries.remove(index);
+        }
+
+        let sequence = queue.next_sequence;
+        queue.next_sequence += 1;
+        queue.entries.push(Entry {
+            priority: self.policy.priority(&outgoing.command),
+            sequence,
+            coalesce_key,
+            outgoing,
+        });
+        self.ready.notify_one();
+    }
+
+    /// Take the next command to write, waiting up to `wait` for one to be queued
+    /// or for the rate limit to allow it
+    pub(crate) fn pop(&self, wait: Duration) -> Option<Outgoing> {
+        let Ok(mut queue) = self.queue.lock() else {
+            return None;
+        };
+        if queue.entries.is_empty() {
+            queue = self.ready.wait_timeout(queue, wait).ok()?.0;
+            if queue.entries.is_empty() {
+                return None;
+            }
+        }
+
+        if let Ok(mut limiter) = self.limiter.lock()
+            && let Some(limiter) = limiter.as_mut()
+            && let Err(delay) = limiter.try_acquire()
+        {
+            drop(queue);
+            std::thread::sleep(delay.min(wait));
+            return None;
+        }
+
+        // Highest priority first, oldest first within a priority
+        let index = queue
+            .entries
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, e)| (e.priority, std::cmp::Reverse(e.sequence)))
+            .map(|(index, _)| index)?;
+        let outgoing = queue.entries.remove(index).outgoing;
+        self.space.notify_one();
+        Some(outgoing)
+    }
+
+    /// Refuse new commands and drop the queued ones, failing their requests
+    pub(crate) fn close(&self) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.closed = true;
+            queue.entries.clear();
+        }
+        self.space.notify_all();
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    activator_state::ActivatorState,
+    acts::ActivatorsData,
+    commands::{InputNumber, RecvCommand, TallyData, TallyResponse},
+};
+use std::collections::HashMap;
+use vmix_core::Vmix;
+
+/// Tally for every mix, merged from `TALLY` and `ACTS` events
+///
+/// vMix only sends `TALLY` for the main mix (mix 1). The tally of mixes 2 to
+/// 16 is derived from the `InputMixN` and `InputPreviewMixN` activators, so
+/// subscribe to both `TALLY` and `ACTS` and feed every event to the tracker.
+///
+/// # Example
+/// ```rust,ignore
+/// use vmix_tcp::{SUBSCRIBECommand, TallyData, TallyTracker, VmixApi};
+///
+/// let vmix = VmixApi::builder(addr)
+///     .subscribe(SUBSCRIBECommand::TALLY)
+///     .subscribe(SUBSCRIBECommand::ACTS)
+///     .build()?;
+/// let mut tally = TallyTracker::new();
+/// while let Ok(event) = vmix.try_receive_command(Duration::from_secs(1)) {
+///     if tally.apply_event(&event) == Some(2) {
+///         let light = tally.input_tally(2, 3);
+///         println!("camera 3 on mix 2: {:?}", light);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TallyTracker {
+    main: Option<HashMap<InputNumber, TallyData>>,
+    activators: ActivatorState,
+}
+
+impl TallyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the tracker with the live and preview inputs of a full XML snapshot
+    pub fn from_snapshot(vmix: &Vmix) -> Self {
+        Self {
+            main: None,
+            activators: ActivatorState::from_snapshot(vmix),
+        }
+    }
+
+    /// Apply a `TALLY` or `ACTS` event
+    ///
+    /// Returns the number of the mix whose tally may have changed.
+    pub fn apply_event(&mut self, event: &RecvCommand) -> Option<u8> {
+        match event {
+            RecvCommand::TALLY(response) => {
+                self.apply_tally(response);
+                Some(1)
+            }
+            RecvCommand::ACTS(response) => self.apply_activator(&response.body),
+            _ => None,
+        }
+    }
+
+    /// Replace the tally of the main mix
+    pub fn apply_tally(&mut self, response: &TallyResponse) {
+        self.main = Some(response.body.clone());
+    }
+
+    /// Apply an activator, returning the mix it belongs to if it affects tally
+    pub fn apply_activator(&mut self, data: &ActivatorsData) -> Option<u8> {
+        let mix = match data {
+            ActivatorsData::InputMix { mix, .. } | ActivatorsData::InputPreviewMix { mix, .. } => {
+                *mix
+            }
+            _ => return None,
+        };
+        self.activators.apply(data);
+        Some(mix)
+    }
+
+    /// Mix numbers with known tally
+    pub fn mixes(&self) -> Vec<u8> {
+        let mut mixes = self.activators.mixes();
+        if self.main.is_some() && !mixes.contains(&1) {
+            mixes.insert(0, 1);
+        }
+        mixes
+    }
+
+    /// Tally of every input on `mix`, where mix 1 is the main output
+    ///
+    /// The main mix uses the latest `TALLY` event when one has been received.
+    /// Inputs known from `TALLY` but not live or in preview are reported as
+    /// [`TallyData::OFF`], so every mix covers the same inputs.
+    pub fn tally(&self, mix: u8) -> HashMap<InputNumber, TallyData> {
+        if mix == 1
+            && let Some(main) = &self.main
+        {
+            return main.clone();
+        }
+
+        let mut tally: HashMap<InputNumber, TallyData> = self
+            .main
+            .iter()
+            .flat_map(|main| main.keys())
+            .map(|input| (*input, TallyData::OFF))
+            .collect();
+        for input in self.activators.preview_inputs(mix) {
+            tally.insert(input, TallyData::PREVIEW);
+        }
+        // An input that is both live and in preview is on air
+        for input in self.activators.live_inputs(mix) {
+            tally.insert(input, TallyData::PROGRAM);
+        }
+        tally
+    }
+
+    /// Tally of a single input on `mix`
+    pub fn input_tally(&self, mix: u8, input: InputNumber) -> TallyData {
+        if mix == 1
+            && let Some(main) = &self.main
+        {
+            return main.get(&input).copied().unwrap_or(TallyData::OFF);
+        }
+
+        if self.activators.is_live(mix, input) {
+            TallyData::PROGRAM
+        } else if self.activators.is_preview(mix, input) {
+            TallyData::PREVIEW
+        } else {
+            TallyData::OFF
+        }
+    }
+}
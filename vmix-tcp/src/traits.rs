@@ -1,16 +1,18 @@
-use crate::commands::RecvCommand;
-use anyhow::Result;
+use crate::commands::{FunctionResponse, RecvCommand, XMLResponse, XMLTextResponse};
+use vmix_core::error::Result;
 
 /// TCP-specific vMix API client trait
 ///
 /// This trait is designed specifically for TCP-based communication
-/// with vMix instances. It focuses on command sending and real-time
-/// event streaming without expecting specific responses to commands.
+/// with vMix instances. It supports both fire-and-forget command sending
+/// with real-time event streaming, and request methods that wait for
+/// the response belonging to a specific command.
 ///
-/// The TCP API is asynchronous by nature - you send commands and
-/// receive events independently. Commands may not have immediate
-/// responses, and other events (ACTS, VERSION, etc.) may arrive
-/// before your command's response.
+/// The TCP API is asynchronous by nature - responses may arrive
+/// interleaved with unsolicited TALLY/ACTS events. The request methods
+/// (`call_function`, `query_xmltext`, `fetch_xml`) match each response to
+/// its pending request in FIFO order per command type; everything else is
+/// delivered through `try_receive_command`.
 pub trait VmixTcpApiClient {
     /// Send a command to vMix (non-blocking)
     ///
@@ -25,6 +27,22 @@ pub trait VmixTcpApiClient {
     /// The events may not correspond to your sent commands and can arrive in any order.
     fn try_receive_command(&self, timeout: std::time::Duration) -> Result<RecvCommand>;
 
+    /// Execute a vMix function and wait for its response (blocking)
+    ///
+    /// Fails if vMix rejects the function or does not respond within `timeout`.
+    fn call_function(
+        &self,
+        function: &str,
+        query: Option<&str>,
+        timeout: std::time::Duration,
+    ) -> Result<FunctionResponse>;
+
+    /// Query a value from the XML state with an XPath expression and wait for the result (blocking)
+    fn query_xmltext(&self, path: &str, timeout: std::time::Duration) -> Result<XMLTextResponse>;
+
+    /// Request the full XML state and wait for it (blocking)
+    fn fetch_xml(&self, timeout: std::time::Duration) -> Result<XMLResponse>;
+
     /// Check if the TCP connection is still alive
     fn is_connected(&self) -> bool;
 
@@ -37,3 +55,42 @@ pub trait VmixTcpApiClient {
     /// Gracefully disconnect from vMix
     fn disconnect(&self) -> Result<()>;
 }
+
+/// Async counterpart of [`VmixTcpApiClient`]
+///
+/// Requests wait for the response belonging to them like the blocking methods,
+/// with the timeout configured on the implementation. Implemented by
+/// [`AsyncVmixApi`](crate::AsyncVmixApi).
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncVmixTcpApiClient {
+    /// Queue a command without waiting for its response
+    async fn send_command(&self, command: crate::commands::SendCommand) -> Result<()>;
+
+    /// Execute a vMix function and wait for its response
+    async fn call_function(&self, function: &str, query: Option<&str>) -> Result<FunctionResponse>;
+
+    /// Execute a typed vMix function and wait for its response
+    async fn execute(&self, function: &vmix_core::VmixFunction) -> Result<FunctionResponse>;
+
+    /// Query a value from the XML state with an XPath expression
+    async fn query_xmltext(&self, path: &str) -> Result<XMLTextResponse>;
+
+    /// Request the full XML state
+    async fn fetch_xml(&self) -> Result<XMLResponse>;
+
+    /// Receive the events matching `filter`, buffering up to `capacity` of them
+    ///
+    /// Await events with [`Subscription::recv_async`](crate::Subscription::recv_async).
+    fn subscribe(
+        &self,
+        filter: crate::events::EventFilter,
+        capacity: usize,
+    ) -> crate::events::EventSubscription;
+
+    /// Check if the TCP connection is still alive
+    fn is_connected(&self) -> bool;
+
+    /// Gracefully disconnect from vMix
+    async fn disconnect(&self) -> Result<()>;
+}
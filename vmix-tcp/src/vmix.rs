@@ -1,8 +1,11 @@
 use crate::{
-    commands::{RecvCommand, SendCommand},
+    builder::VmixApiBuilder,
+    commands::{FunctionResponse, RecvCommand, SendCommand, Status, XMLResponse, XMLTextResponse},
+    events::{Broadcaster, EventFilter, EventSubscription, RecvError},
+    pending::{PendingRequests, ResponseKind},
+    queue::{CommandQueue, Outgoing},
     traits::VmixTcpApiClient,
 };
-use anyhow::Result;
 use std::{
     io::Write,
     net::{SocketAddr, TcpStream},
@@ -12,15 +15,26 @@ use std::{
         mpsc::{Receiver, SyncSender},
     },
     thread::JoinHandle,
-    time::Duration,
+    time::{Duration, Instant},
+};
+use vmix_core::{
+    VmixFunction,
+    error::{Result, VmixError},
+    query::FunctionParams,
+    version::{Capabilities, VmixVersion},
 };
 
 pub struct VmixApi {
     pub sender: SyncSender<SendCommand>,
-    pub receiver: Receiver<RecvCommand>,
+    events: Arc<Broadcaster<RecvCommand>>,
+    default_subscription: EventSubscription,
     shutdown_signal: Arc<AtomicBool>,
     error_signal: Arc<AtomicBool>, // New: shared error state
     original_stream: Arc<std::sync::Mutex<Option<TcpStream>>>, // New: keep original stream for explicit shutdown
+    shutdown_timeout: Duration,
+    queue: Arc<CommandQueue>,
+    capabilities: std::sync::Mutex<Option<Capabilities>>,
+    last_received: Arc<std::sync::Mutex<Option<Instant>>>,
     reader_handle: Option<JoinHandle<()>>,
     writer_handle: Option<JoinHandle<()>>,
 }
@@ -48,7 +62,7 @@ impl Drop for VmixApi {
         let _ = self.sender.try_send(SendCommand::QUIT);
 
         // Wait for threads to complete with timeout
-        let shutdown_timeout = Duration::from_millis(500);
+        let shutdown_timeout = self.shutdown_timeout;
 
         // Join reader thread with timeout
         if let Some(handle) = self.reader_handle.take() {
@@ -60,12 +74,12 @@ impl Drop for VmixApi {
             loop {
                 if handle.is_finished() {
                     if handle.join().is_err() {
-                        eprintln!("Warning: Reader thread panicked during shutdown");
+                        log_warn!("reader thread panicked during shutdown");
                     }
                     break;
                 }
                 if start_time.elapsed() > shutdown_timeout {
-                    eprintln!("Warning: Reader thread did not shut down within timeout");
+                    log_warn!("reader thread did not shut down within timeout");
                     break;
                 }
                 std::thread::sleep(Duration::from_millis(10));
@@ -78,12 +92,12 @@ impl Drop for VmixApi {
             loop {
                 if handle.is_finished() {
                     if handle.join().is_err() {
-                        eprintln!("Warning: Writer thread panicked during shutdown");
+                        log_warn!("writer thread panicked during shutdown");
                     }
                     break;
                 }
                 if start_time.elapsed() > shutdown_timeout {
-                    eprintln!("Warning: Writer thread did not shut down within timeout");
+                    log_warn!("writer thread did not shut down within timeout");
                     break;
                 }
                 std::thread::sleep(Duration::from_millis(10));
@@ -92,45 +106,129 @@ impl Drop for VmixApi {
     }
 }
 
+/// How long to wait for the greeting vMix sends when a connection is opened
+const GREETING_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Read the `VERSION OK <version>` line vMix sends when a connection is opened
+///
+/// Anything else received first is returned to be published as an event. A server
+/// that sends nothing within `timeout` is assumed not to greet.
+fn read_greeting(
+    stream: &mut TcpStream,
+    timeout: Duration,
+    read_timeout: Duration,
+    xml_read_timeout: Duration,
+) -> Result<Option<RecvCommand>> {
+    stream.set_read_timeout(Some(timeout))?;
+    let received = RecvCommand::read_from(stream, xml_read_timeout);
+    stream.set_read_timeout(Some(read_timeout))?;
+    match received {
+        Ok(RecvCommand::VERSION(_greeting)) => {
+            log_debug!(version = ?_greeting.version, "received greeting");
+            Ok(None)
+        }
+        Ok(command) => Ok(Some(command)),
+        Err(VmixError::Io(e))
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(e @ (VmixError::Io(_) | VmixError::Disconnected)) => Err(e),
+        Err(_err) => {
+            log_warn!(error = %_err, "failed to parse greeting");
+            Ok(None)
+        }
+    }
+}
+
 impl VmixApi {
+    /// Connect to vMix using `timeout` for connecting, reading and writing
+    ///
+    /// Use [`VmixApi::builder`] to tune timeouts, buffers and socket options individually.
+    /// Waits up to 500ms for the version vMix greets new connections with.
     pub fn new(remote: SocketAddr, timeout: Duration) -> Result<Self> {
+        Self::builder(remote).timeout(timeout).build()
+    }
+
+    /// Create a builder for a connection to `remote`
+    pub fn builder(remote: SocketAddr) -> VmixApiBuilder {
+        VmixApiBuilder::new(remote)
+    }
+
+    pub(crate) fn connect(config: VmixApiBuilder) -> Result<Self> {
+        let remote = config.remote;
+
         // Connect with proper error handling
-        let stream = TcpStream::connect_timeout(&remote, timeout)
-            .map_err(|e| anyhow::anyhow!("Failed to connect to {}: {}", remote, e))?;
+        let mut stream =
+            TcpStream::connect_timeout(&remote, config.connect_timeout).map_err(|e| {
+                VmixError::Connect {
+                    addr: remote.to_string(),
+                    source: e,
+                }
+            })?;
+
+        stream.set_read_timeout(Some(config.read_timeout))?;
+        stream.set_write_timeout(Some(config.write_timeout))?;
+        stream.set_nodelay(config.tcp_nodelay)?;
 
-        stream
-            .set_read_timeout(Some(timeout))
-            .map_err(|e| anyhow::anyhow!("Failed to set read timeout: {}", e))?;
+        if let Some(idle) = config.tcp_keepalive {
+            socket2::SockRef::from(&stream)
+                .set_tcp_keepalive(&socket2::TcpKeepalive::new().with_time(idle))?;
+        }
 
-        stream
-            .set_write_timeout(Some(timeout))
-            .map_err(|e| anyhow::anyhow!("Failed to set write timeout: {}", e))?;
+        // vMix greets every connection with its version. Read it before any request is
+        // sent, so that it cannot be taken for the response to a VERSION request.
+        let greeting = read_greeting(
+            &mut stream,
+            config.read_timeout.min(GREETING_TIMEOUT),
+            config.read_timeout,
+            config.xml_read_timeout,
+        )?;
 
         // Create stream clones for reader and writer
-        let mut reader_stream = stream
-            .try_clone()
-            .map_err(|e| anyhow::anyhow!("Failed to clone stream for reader: {}", e))?;
+        let mut reader_stream = stream.try_clone()?;
 
         // Set reader stream to non-blocking mode for better shutdown handling
-        reader_stream
-            .set_nonblocking(true)
-            .map_err(|e| anyhow::anyhow!("Failed to set non-blocking mode: {}", e))?;
-        let writer_stream = stream
-            .try_clone()
-            .map_err(|e| anyhow::anyhow!("Failed to clone stream for writer: {}", e))?;
+        reader_stream.set_nonblocking(true)?;
+        let writer_stream = stream.try_clone()?;
 
         // Shared signals for coordination
         let shutdown_signal = Arc::new(AtomicBool::new(false));
         let error_signal = Arc::new(AtomicBool::new(false));
         let original_stream = Arc::new(std::sync::Mutex::new(Some(stream)));
+        let pending = Arc::new(std::sync::Mutex::new(PendingRequests::default()));
+        let last_received = Arc::new(std::sync::Mutex::new(None));
+
+        log_debug!(remote = %remote, "connected to vMix");
+        #[cfg(feature = "tracing")]
+        let connection_span = tracing::info_span!("vmix_tcp", remote = %remote);
 
         // Reader thread setup
-        let (reader_sender, reader_receiver): (SyncSender<RecvCommand>, Receiver<RecvCommand>) =
-            std::sync::mpsc::sync_channel(1);
+        let events = Arc::new(Broadcaster::new());
+        let default_subscription = events.subscribe(config.event_buffer, |_| true);
+        if let Some(command) = greeting {
+            events.publish(&command);
+        }
+        let xml_read_timeout = config.xml_read_timeout;
 
+        let reader_events = events.clone();
         let reader_shutdown = shutdown_signal.clone();
         let reader_error = error_signal.clone();
+        let reader_pending = pending.clone();
+        let reader_last_received = last_received.clone();
+        #[cfg(feature = "tracing")]
+        let reader_span = connection_span.clone();
+        let queue = Arc::new(CommandQueue::new(
+            config.queue_policy,
+            config.command_buffer,
+        ));
+
         let reader_handle = std::thread::spawn(move || {
+            #[cfg(feature = "tracing")]
+            let _span = reader_span.enter();
             loop {
                 // Check shutdown or error signals at the beginning of each iteration
                 if reader_shutdown.load(Ordering::Relaxed) || reader_error.load(Ordering::Relaxed) {
@@ -139,56 +237,78 @@ impl VmixApi {
 
                 // Try to read from stream with timeout handling
                 // Note: This may still block, but the stream will be closed from Drop impl
-                match (&mut reader_stream).try_into() {
+                match RecvCommand::read_from(&mut reader_stream, xml_read_timeout) {
                     Ok(command) => {
-                        if reader_sender.send(command).is_err() {
-                            // Receiver dropped, exit thread
-                            break;
+                        log_trace!(kind = ?command.kind(), "received");
+                        if let Ok(mut last_received) = reader_last_received.lock() {
+                            *last_received = Some(Instant::now());
+                        }
+                        // Responses to call_function/query_xmltext/fetch_xml go to their caller,
+                        // everything else is forwarded to the event stream
+                        let command = match reader_pending.lock() {
+                            Ok(mut pending) => pending.resolve(command),
+                            Err(_) => Some(command),
+                        };
+                        if let Some(command) = command {
+                            reader_events.publish(&command);
                         }
                     }
-                    Err(err) => {
-                        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
-                            match io_err.kind() {
-                                std::io::ErrorKind::WouldBlock => {
-                                    // Non-blocking operation would block, sleep briefly and retry
-                                    std::thread::sleep(Duration::from_millis(10));
-                                    continue;
-                                }
-                                std::io::ErrorKind::TimedOut => {
-                                    // Timeout is expected, continue loop
-                                    continue;
-                                }
-                                std::io::ErrorKind::ConnectionAborted
-                                | std::io::ErrorKind::ConnectionReset
-                                | std::io::ErrorKind::UnexpectedEof => {
-                                    // Connection error - signal error to writer thread
-                                    eprintln!("Connection closed by remote: {}", err);
-                                    reader_error.store(true, Ordering::Relaxed);
-                                    break;
-                                }
-                                _ => {
-                                    // Other IO errors - signal error to writer thread
-                                    eprintln!("IO error in reader thread: {}", err);
-                                    reader_error.store(true, Ordering::Relaxed);
-                                    break;
-                                }
-                            }
-                        } else {
-                            eprintln!("Failed to parse incoming packet: {}", err);
+                    Err(VmixError::Io(io_err)) => match io_err.kind() {
+                        std::io::ErrorKind::WouldBlock => {
+                            // Non-blocking operation would block, sleep briefly and retry
+                            std::thread::sleep(Duration::from_millis(10));
+                            continue;
+                        }
+                        std::io::ErrorKind::TimedOut => {
+                            // Timeout is expected, continue loop
                             continue;
                         }
+                        std::io::ErrorKind::ConnectionAborted
+                        | std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::UnexpectedEof => {
+                            // Connection error - signal error to writer thread
+                            log_debug!(error = %io_err, "connection closed by remote");
+                            reader_error.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                        _ => {
+                            // Other IO errors - signal error to writer thread
+                            log_warn!(error = %io_err, "I/O error in reader thread");
+                            reader_error.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    },
+                    Err(VmixError::Disconnected) => {
+                        // Connection error - signal error to writer thread
+                        log_debug!("connection closed by remote");
+                        reader_error.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                    Err(_err) => {
+                        log_warn!(error = %_err, "failed to parse incoming packet");
+                        continue;
                     }
                 }
             }
+
+            // Nobody will answer outstanding requests anymore
+            if let Ok(mut pending) = reader_pending.lock() {
+                pending.clear();
+            }
+            reader_events.close();
         });
 
         // Writer thread setup
         let (writer_sender, writer_receiver): (SyncSender<SendCommand>, Receiver<SendCommand>) =
-            std::sync::mpsc::sync_channel(1);
+            std::sync::mpsc::sync_channel(config.command_buffer);
 
         let writer_shutdown = shutdown_signal.clone();
         let writer_error = error_signal.clone();
+        let writer_queue = queue.clone();
+        let writer_pending = pending.clone();
         let writer_handle = std::thread::spawn(move || {
+            #[cfg(feature = "tracing")]
+            let _span = connection_span.enter();
             let mut writer = writer_stream;
 
             loop {
@@ -197,67 +317,286 @@ impl VmixApi {
                     break;
                 }
 
-                // Try to receive with timeout to allow checking signals
-                match writer_receiver.recv_timeout(Duration::from_millis(10)) {
-                    Ok(command) => {
-                        // Check for quit command
-                        if matches!(command, SendCommand::QUIT) {
-                            let bytes: Vec<u8> = command.into();
-                            if writer.write_all(&bytes).is_err() {
-                                writer_error.store(true, Ordering::Relaxed);
-                            }
-                            // Quit command processed, exit thread
-                            break;
-                        }
-
-                        let bytes: Vec<u8> = command.into();
-                        if writer.write_all(&bytes).is_err() {
-                            eprintln!("Failed to write to stream");
-                            writer_error.store(true, Ordering::Relaxed);
-                            break;
-                        }
+                // Commands sent through `sender` join the queue so priorities apply to them too.
+                // They stay in the channel while the queue is full, so `sender` blocks.
+                writer_queue.push_from(&writer_receiver);
+
+                // Wait briefly so the signals and `sender` are checked regularly
+                let Some(Outgoing { command, reply }) = writer_queue.pop(Duration::from_millis(10))
+                else {
+                    continue;
+                };
+
+                #[cfg(feature = "tracing")]
+                let _command_span = match &command {
+                    SendCommand::FUNCTION(function, _) => tracing::debug_span!(
+                        "command",
+                        command = command.name(),
+                        function = %function
+                    ),
+                    _ => tracing::debug_span!("command", command = command.name()),
+                }
+                .entered();
 
-                        if writer.flush().is_err() {
-                            eprintln!("Failed to flush stream");
-                            writer_error.store(true, Ordering::Relaxed);
-                            break;
-                        }
+                // Register before writing so the response cannot arrive first
+                if let Some(kind) = ResponseKind::of_request(&command) {
+                    match writer_pending.lock() {
+                        Ok(mut pending) => pending.register(kind, reply),
+                        Err(_) => break,
                     }
-                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                        // Timeout, continue to check signals
-                        continue;
-                    }
-                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
-                        // Channel disconnected, exit thread
-                        break;
+                }
+
+                // Check for quit command
+                if matches!(command, SendCommand::QUIT) {
+                    let bytes: Vec<u8> = command.into();
+                    if writer.write_all(&bytes).is_err() {
+                        writer_error.store(true, Ordering::Relaxed);
                     }
+                    // Quit command processed, exit thread
+                    break;
+                }
+
+                let bytes: Vec<u8> = command.into();
+                log_trace!(bytes = bytes.len(), "sending");
+                if let Err(_err) = writer.write_all(&bytes) {
+                    log_warn!(error = %_err, "failed to write to stream");
+                    writer_error.store(true, Ordering::Relaxed);
+                    break;
+                }
+
+                if let Err(_err) = writer.flush() {
+                    log_warn!(error = %_err, "failed to flush stream");
+                    writer_error.store(true, Ordering::Relaxed);
+                    break;
                 }
             }
+
+            // Requests still queued fail with `Disconnected`
+            writer_queue.close();
         });
 
+        for command in config.subscriptions {
+            writer_sender
+                .send(SendCommand::SUBSCRIBE(command))
+                .map_err(|_| VmixError::Disconnected)?;
+        }
+
         Ok(Self {
             sender: writer_sender,
-            receiver: reader_receiver,
+            events,
+            default_subscription,
             shutdown_signal,
             error_signal,
             original_stream,
+            shutdown_timeout: config.shutdown_timeout,
+            queue,
+            capabilities: std::sync::Mutex::new(None),
+            last_received,
             reader_handle: Some(reader_handle),
             writer_handle: Some(writer_handle),
         })
     }
 
-    /// Send a command to vMix
+    /// Queue a command for vMix without waiting for its response
+    ///
+    /// Commands are written in the order they were queued unless priorities are
+    /// configured, see [`CommandPriority`](crate::CommandPriority), and queued
+    /// updates of continuous controls such as `SetVolume` for the same input are
+    /// replaced by the latest one. Blocks while the queue is full, see
+    /// [`VmixApiBuilder::command_buffer`].
     pub fn send_command(&self, command: SendCommand) -> Result<()> {
-        self.sender
-            .send(command)
-            .map_err(|e| anyhow::anyhow!("Failed to send command: {}", e))
+        self.queue.push(
+            Outgoing {
+                command,
+                reply: None,
+            },
+            None,
+        )
     }
 
     /// Try to receive a command from vMix with timeout
+    ///
+    /// Responses consumed by `call_function`, `query_xmltext` and `fetch_xml`
+    /// are not delivered here.
     pub fn try_receive_command(&self, timeout: Duration) -> Result<RecvCommand> {
-        self.receiver
-            .recv_timeout(timeout)
-            .map_err(|e| anyhow::anyhow!("Failed to receive command: {}", e))
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.default_subscription.recv_timeout(remaining) {
+                Ok(command) => return Ok(command),
+                // This stream has no way to report gaps, keep going with the next event
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Timeout) => {
+                    return Err(VmixError::Timeout("waiting for event".to_string()));
+                }
+                Err(RecvError::Closed) => return Err(VmixError::Disconnected),
+            }
+        }
+    }
+
+    /// Subscribe to events from vMix
+    ///
+    /// Every subscription receives its own copy of each event matching `filter`,
+    /// independently of other subscribers and of `try_receive_command`. Up to
+    /// `capacity` events are buffered; if the subscriber falls further behind,
+    /// the oldest events are dropped and the next receive reports
+    /// [`RecvError::Lagged`] with the number of missed events.
+    ///
+    /// Responses to `call_function`, `query_xmltext` and `fetch_xml` are not
+    /// events, even when they arrive after the request timed out.
+    pub fn subscribe(&self, filter: EventFilter, capacity: usize) -> EventSubscription {
+        self.events
+            .subscribe(capacity, move |event| filter.matches(event))
+    }
+
+    /// Send a command and wait for the response that belongs to it
+    ///
+    /// Responses are paired with requests in FIFO order per command type, so
+    /// unsolicited TALLY/ACTS events arriving in between are left for
+    /// `try_receive_command`.
+    fn request(&self, command: SendCommand, timeout: Duration) -> Result<RecvCommand> {
+        let kind = ResponseKind::of_request(&command).ok_or_else(|| {
+            VmixError::Protocol("command does not have a correlated response".to_string())
+        })?;
+
+        // The writer registers the reply channel when the command goes on the wire,
+        // so the pending order matches the wire order whatever the queue does
+        let (reply, receiver) = std::sync::mpsc::sync_channel(1);
+        let deadline = Instant::now() + timeout;
+        self.queue.push(
+            Outgoing {
+                command,
+                reply: Some(reply),
+            },
+            Some(deadline),
+        )?;
+        let _sent_at = Instant::now();
+
+        let response = receiver
+            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            .map_err(|e| match e {
+                std::sync::mpsc::RecvTimeoutError::Timeout => {
+                    log_debug!(kind = ?kind, "request timed out");
+                    VmixError::Timeout(format!("waiting for {:?} response", kind))
+                }
+                std::sync::mpsc::RecvTimeoutError::Disconnected => VmixError::Disconnected,
+            })?;
+        log_debug!(
+            kind = ?kind,
+            latency_ms = _sent_at.elapsed().as_secs_f64() * 1000.0,
+            "response received"
+        );
+        Ok(response)
+    }
+
+    /// Execute a vMix function and wait for vMix to acknowledge it
+    ///
+    /// `query` is sent verbatim and must already be percent-encoded; use
+    /// [`call_function_with_params`](Self::call_function_with_params) to have it encoded.
+    /// Returns an error if vMix answers with `ER` or does not answer within `timeout`.
+    pub fn call_function(
+        &self,
+        function: &str,
+        query: Option<&str>,
+        timeout: Duration,
+    ) -> Result<FunctionResponse> {
+        let command = SendCommand::FUNCTION(function.to_string(), query.map(str::to_string));
+        self.send_function(command, timeout)
+    }
+
+    /// Execute a vMix function with parameters percent-encoded like the HTTP client's
+    pub fn call_function_with_params(
+        &self,
+        function: &str,
+        params: &FunctionParams,
+        timeout: Duration,
+    ) -> Result<FunctionResponse> {
+        self.send_function(SendCommand::function(function, params), timeout)
+    }
+
+    /// Execute a typed vMix function and wait for vMix to acknowledge it
+    ///
+    /// Once the capabilities are known, functions the connected vMix does not support
+    /// fail with [`VmixError::Unsupported`] without being sent.
+    pub fn execute(&self, function: &VmixFunction, timeout: Duration) -> Result<FunctionResponse> {
+        if let Some(capabilities) = self.capabilities() {
+            capabilities.check(function)?;
+        }
+        self.send_function(function.into(), timeout)
+    }
+
+    fn send_function(&self, command: SendCommand, timeout: Duration) -> Result<FunctionResponse> {
+        match self.request(command, timeout)? {
+            RecvCommand::FUNCTION(response) => match response.status {
+                Status::ER => Err(VmixError::VmixRejected {
+                    message: response.body.unwrap_or_default(),
+                }),
+                _ => Ok(response),
+            },
+            other => Err(VmixError::Protocol(format!(
+                "unexpected response: {:?}",
+                other.kind()
+            ))),
+        }
+    }
+
+    /// Query a single value from the vMix XML state using an XPath expression
+    pub fn query_xmltext(&self, path: &str, timeout: Duration) -> Result<XMLTextResponse> {
+        match self.request(SendCommand::XMLTEXT(path.to_string()), timeout)? {
+            RecvCommand::XMLTEXT(response) => match response.status {
+                Status::ER => Err(VmixError::VmixRejected {
+                    message: response.body.unwrap_or_default(),
+                }),
+                _ => Ok(response),
+            },
+            other => Err(VmixError::Protocol(format!(
+                "unexpected response: {:?}",
+                other.kind()
+            ))),
+        }
+    }
+
+    /// Fetch the complete vMix XML state
+    pub fn fetch_xml(&self, timeout: Duration) -> Result<XMLResponse> {
+        match self.request(SendCommand::XML, timeout)? {
+            RecvCommand::XML(response) => Ok(response),
+            other => Err(VmixError::Protocol(format!(
+                "unexpected response: {:?}",
+                other.kind()
+            ))),
+        }
+    }
+
+    /// Ask vMix for its version
+    pub fn version(&self, timeout: Duration) -> Result<VmixVersion> {
+        match self.request(SendCommand::VERSION, timeout)? {
+            RecvCommand::VERSION(response) => response.parsed().ok_or_else(|| {
+                VmixError::Protocol(format!("invalid version: {:?}", response.version))
+            }),
+            other => Err(VmixError::Protocol(format!(
+                "unexpected response: {:?}",
+                other.kind()
+            ))),
+        }
+    }
+
+    /// Query the vMix version and remember its capabilities for [`execute`](Self::execute)
+    pub fn detect_capabilities(&self, timeout: Duration) -> Result<Capabilities> {
+        let capabilities = Capabilities::new(self.version(timeout)?);
+        self.set_capabilities(Some(capabilities));
+        Ok(capabilities)
+    }
+
+    /// Capabilities used to check functions before they are sent, if known
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.capabilities.lock().ok().and_then(|guard| *guard)
+    }
+
+    /// Set or clear the capabilities, e.g. from the version of an XML snapshot
+    pub fn set_capabilities(&self, capabilities: Option<Capabilities>) {
+        if let Ok(mut guard) = self.capabilities.lock() {
+            *guard = capabilities;
+        }
     }
 
     /// Gracefully disconnect from vMix
@@ -265,7 +604,16 @@ impl VmixApi {
         self.send_command(SendCommand::QUIT)
     }
 
+    /// When the last complete message was received from vMix
+    pub fn last_received(&self) -> Option<Instant> {
+        self.last_received.lock().ok().and_then(|guard| *guard)
+    }
+
     /// Check if the connection is still alive
+    ///
+    /// This only detects connections that were closed or reset. A half-open
+    /// connection still looks connected; use a [`HealthMonitor`](crate::HealthMonitor)
+    /// to detect a vMix that stopped answering.
     pub fn is_connected(&self) -> bool {
         // First check atomic flags for immediate shutdown/error detection
         if self.shutdown_signal.load(Ordering::Relaxed) || self.error_signal.load(Ordering::Relaxed)
@@ -325,6 +673,23 @@ impl VmixTcpApiClient for VmixApi {
         self.try_receive_command(timeout)
     }
 
+    fn call_function(
+        &self,
+        function: &str,
+        query: Option<&str>,
+        timeout: Duration,
+    ) -> Result<FunctionResponse> {
+        self.call_function(function, query, timeout)
+    }
+
+    fn query_xmltext(&self, path: &str, timeout: Duration) -> Result<XMLTextResponse> {
+        self.query_xmltext(path, timeout)
+    }
+
+    fn fetch_xml(&self, timeout: Duration) -> Result<XMLResponse> {
+        self.fetch_xml(timeout)
+    }
+
     fn is_connected(&self) -> bool {
         self.is_connected()
     }